@@ -0,0 +1,343 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::parsing::ElfInventory;
+
+// Above this many items the exact search can take forever, so the automatic
+// method falls back to the heuristics and the exact one is refused.
+const EXACT_ITEM_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceMethod {
+    Auto,
+    Exact,
+    Lpt,
+    KarmarkarKarp,
+}
+
+impl FromStr for BalanceMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(BalanceMethod::Auto),
+            "exact" => Ok(BalanceMethod::Exact),
+            "lpt" => Ok(BalanceMethod::Lpt),
+            "kk" => Ok(BalanceMethod::KarmarkarKarp),
+            _ => Err(format!("{} is not a valid balancing method", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BalanceError {
+    NoElves,
+    NegativeItem(i32),
+    LoadOverflow,
+    TooManyItemsForExact(usize),
+}
+
+impl Display for BalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BalanceError::NoElves => write!(f, "items can't be balanced across zero elves"),
+            BalanceError::NegativeItem(item) => {
+                write!(f, "can't balance negative calorie count {}", item)
+            }
+            BalanceError::LoadOverflow => write!(f, "an elf's load doesn't fit in an i32"),
+            BalanceError::TooManyItemsForExact(count) => write!(
+                f,
+                "the exact method handles at most {} items, found {} (use --method auto, lpt or kk)",
+                EXACT_ITEM_LIMIT, count
+            ),
+        }
+    }
+}
+
+impl Error for BalanceError {}
+
+pub fn balance_items(
+    items: &[i32],
+    elf_count: usize,
+    method: BalanceMethod,
+) -> Result<Vec<ElfInventory>, BalanceError> {
+    if elf_count == 0 {
+        return Err(BalanceError::NoElves);
+    }
+    if let Some(&item) = items.iter().find(|&&item| item < 0) {
+        return Err(BalanceError::NegativeItem(item));
+    }
+    if method == BalanceMethod::Exact && items.len() > EXACT_ITEM_LIMIT {
+        return Err(BalanceError::TooManyItemsForExact(items.len()));
+    }
+
+    let mut items = items.to_vec();
+    items.sort_by_key(|&item| Reverse(item));
+
+    let elves = match method {
+        BalanceMethod::Exact => branch_and_bound(&items, elf_count),
+        BalanceMethod::Lpt => longest_processing_time(&items, elf_count),
+        BalanceMethod::KarmarkarKarp => karmarkar_karp(&items, elf_count),
+        BalanceMethod::Auto if items.len() <= EXACT_ITEM_LIMIT => {
+            branch_and_bound(&items, elf_count)
+        }
+        BalanceMethod::Auto => {
+            let lpt = longest_processing_time(&items, elf_count);
+            let kk = karmarkar_karp(&items, elf_count);
+            if max_load(&kk) < max_load(&lpt) {
+                kk
+            } else {
+                lpt
+            }
+        }
+    };
+
+    elves
+        .into_iter()
+        .map(|items| {
+            let total = items.iter().map(|&item| item as i64).sum::<i64>();
+            Ok(ElfInventory {
+                total: i32::try_from(total).map_err(|_| BalanceError::LoadOverflow)?,
+                items,
+            })
+        })
+        .collect()
+}
+
+pub fn load_spread(elves: &[ElfInventory]) -> (i32, i32) {
+    let max = elves.iter().map(|elf| elf.total).max().unwrap_or(0);
+    let min = elves.iter().map(|elf| elf.total).min().unwrap_or(0);
+    (max, min)
+}
+
+fn load(items: &[i32]) -> i64 {
+    items.iter().map(|&item| item as i64).sum()
+}
+
+fn max_load(elves: &[Vec<i32>]) -> i64 {
+    elves.iter().map(|items| load(items)).max().unwrap_or(0)
+}
+
+// Items are expected in decreasing order, each one goes to the least loaded elf.
+fn longest_processing_time(items: &[i32], elf_count: usize) -> Vec<Vec<i32>> {
+    let mut elves = vec![vec![]; elf_count];
+    let mut loads: BinaryHeap<_> = (0..elf_count).map(|elf| Reverse((0i64, elf))).collect();
+
+    for &item in items {
+        let Reverse((load, elf)) = loads.pop().unwrap();
+        elves[elf].push(item);
+        loads.push(Reverse((load + item as i64, elf)));
+    }
+
+    elves
+}
+
+struct Partition {
+    // Sorted from the heaviest subset to the lightest one.
+    subsets: Vec<(i64, Vec<i32>)>,
+}
+
+impl Partition {
+    fn spread(&self) -> i64 {
+        self.subsets.first().unwrap().0 - self.subsets.last().unwrap().0
+    }
+
+    // The heaviest subsets of one side are paired with the lightest subsets of
+    // the other so that both spreads cancel out as much as possible.
+    fn combine(self, other: Partition) -> Partition {
+        let mut subsets: Vec<_> = self
+            .subsets
+            .into_iter()
+            .zip(other.subsets.into_iter().rev())
+            .map(|((load_1, mut items_1), (load_2, items_2))| {
+                items_1.extend(items_2);
+                (load_1 + load_2, items_1)
+            })
+            .collect();
+        subsets.sort_by_key(|(load, _)| Reverse(*load));
+        Partition { subsets }
+    }
+}
+
+// Multi-way largest differencing method: repeatedly merge the two partitions
+// with the largest spreads until a single one remains.
+fn karmarkar_karp(items: &[i32], elf_count: usize) -> Vec<Vec<i32>> {
+    let mut partitions: Vec<Option<Partition>> = items
+        .iter()
+        .map(|&item| {
+            let mut subsets = vec![(item as i64, vec![item])];
+            subsets.resize(elf_count, (0, vec![]));
+            Some(Partition { subsets })
+        })
+        .collect();
+    let mut by_spread: BinaryHeap<_> = partitions
+        .iter()
+        .enumerate()
+        .map(|(id, partition)| (partition.as_ref().unwrap().spread(), id))
+        .collect();
+
+    while by_spread.len() > 1 {
+        let (_, id_1) = by_spread.pop().unwrap();
+        let (_, id_2) = by_spread.pop().unwrap();
+        let partition_1 = partitions[id_1].take().unwrap();
+        let partition_2 = partitions[id_2].take().unwrap();
+
+        let combined = partition_1.combine(partition_2);
+        by_spread.push((combined.spread(), id_1));
+        partitions[id_1] = Some(combined);
+    }
+
+    match by_spread.pop() {
+        Some((_, id)) => partitions[id]
+            .take()
+            .unwrap()
+            .subsets
+            .into_iter()
+            .map(|(_, items)| items)
+            .collect(),
+        None => vec![vec![]; elf_count],
+    }
+}
+
+struct Search<'a> {
+    items: &'a [i32],
+    lower_bound: i64,
+    loads: Vec<i64>,
+    assignment: Vec<usize>,
+    best_max_load: i64,
+    best_assignment: Option<Vec<usize>>,
+}
+
+impl Search<'_> {
+    // Returns true once a partition reaching the lower bound has been found,
+    // since nothing can beat it.
+    fn explore(&mut self, i: usize) -> bool {
+        if i == self.items.len() {
+            self.best_max_load = *self.loads.iter().max().unwrap();
+            self.best_assignment = Some(self.assignment.clone());
+            return self.best_max_load == self.lower_bound;
+        }
+
+        let item = self.items[i] as i64;
+        for elf in 0..self.loads.len() {
+            if self.loads[elf] + item >= self.best_max_load {
+                continue;
+            }
+            // Elves with the same load are interchangeable.
+            if self.loads[..elf].contains(&self.loads[elf]) {
+                continue;
+            }
+
+            self.loads[elf] += item;
+            self.assignment[i] = elf;
+            let optimal = self.explore(i + 1);
+            self.loads[elf] -= item;
+
+            if optimal {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+// Items are expected in decreasing order so that the search fails fast.
+fn branch_and_bound(items: &[i32], elf_count: usize) -> Vec<Vec<i32>> {
+    let greedy = longest_processing_time(items, elf_count);
+    let total = load(items);
+    let lower_bound = items
+        .first()
+        .map(|&item| item as i64)
+        .unwrap_or(0)
+        .max((total + elf_count as i64 - 1) / elf_count as i64);
+
+    let mut search = Search {
+        items,
+        lower_bound,
+        loads: vec![0; elf_count],
+        assignment: vec![0; items.len()],
+        best_max_load: max_load(&greedy),
+        best_assignment: None,
+    };
+    if search.best_max_load > lower_bound {
+        search.explore(0);
+    }
+
+    match search.best_assignment {
+        Some(assignment) => {
+            let mut elves = vec![vec![]; elf_count];
+            for (&item, elf) in items.iter().zip(assignment) {
+                elves[elf].push(item);
+            }
+            elves
+        }
+        None => greedy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{balance_items, load_spread, BalanceError, BalanceMethod};
+
+    const ITEMS: [i32; 5] = [4, 8, 5, 7, 6];
+
+    fn sorted_items(elves: &[crate::parsing::ElfInventory]) -> Vec<i32> {
+        let mut items: Vec<_> = elves.iter().flat_map(|elf| elf.items.clone()).collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn exact_method_finds_the_optimal_max_load() {
+        let elves = balance_items(&ITEMS, 2, BalanceMethod::Exact).unwrap();
+
+        assert_eq!(load_spread(&elves), (15, 15));
+        assert_eq!(sorted_items(&elves), vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn lpt_assigns_each_item_to_the_least_loaded_elf() {
+        let elves = balance_items(&ITEMS, 2, BalanceMethod::Lpt).unwrap();
+
+        assert_eq!(load_spread(&elves), (17, 13));
+    }
+
+    #[test]
+    fn karmarkar_karp_differences_the_largest_items_first() {
+        let elves = balance_items(&ITEMS, 2, BalanceMethod::KarmarkarKarp).unwrap();
+
+        assert_eq!(load_spread(&elves), (16, 14));
+        assert_eq!(sorted_items(&elves), vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn every_elf_is_kept_even_without_items() {
+        let elves = balance_items(&[3], 3, BalanceMethod::Auto).unwrap();
+
+        assert_eq!(elves.len(), 3);
+        assert_eq!(load_spread(&elves), (3, 0));
+    }
+
+    #[test]
+    fn negative_items_can_not_be_balanced() {
+        let result = balance_items(&[3, -1], 2, BalanceMethod::Auto);
+
+        assert_eq!(result, Err(BalanceError::NegativeItem(-1)));
+    }
+
+    #[test]
+    fn exact_method_refuses_too_many_items() {
+        let items = [1; 21];
+
+        let result = balance_items(&items, 2, BalanceMethod::Exact);
+
+        assert_eq!(result, Err(BalanceError::TooManyItemsForExact(21)));
+        assert!(balance_items(&items[..20], 2, BalanceMethod::Exact).is_ok());
+    }
+}
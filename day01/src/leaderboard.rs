@@ -0,0 +1,224 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+use crate::parsing::{parse_inventories, GroupParser, InventoryParseError, ParseMode};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub index: usize,
+    pub total: i32,
+}
+
+#[derive(Debug)]
+pub enum LeaderboardError {
+    Io(io::Error),
+    Parse(InventoryParseError),
+    NotEnoughElves { requested: usize, available: usize },
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaderboardError::Io(err) => write!(f, "could not read inventory: {}", err),
+            LeaderboardError::Parse(err) => write!(f, "{}", err),
+            LeaderboardError::NotEnoughElves {
+                requested,
+                available,
+            } => write!(
+                f,
+                "asked for the top {} elves but the inventory only has {}",
+                requested, available
+            ),
+        }
+    }
+}
+
+impl Error for LeaderboardError {}
+
+impl From<io::Error> for LeaderboardError {
+    fn from(err: io::Error) -> Self {
+        LeaderboardError::Io(err)
+    }
+}
+
+impl From<InventoryParseError> for LeaderboardError {
+    fn from(err: InventoryParseError) -> Self {
+        LeaderboardError::Parse(err)
+    }
+}
+
+// Ties are broken in favour of the elf that comes first in the file.
+type HeapEntry = Reverse<(i32, Reverse<usize>)>;
+
+pub struct Leaderboard {
+    capacity: usize,
+    heap: BinaryHeap<HeapEntry>,
+    elf_count: usize,
+}
+
+impl Leaderboard {
+    pub fn new(capacity: usize) -> Leaderboard {
+        Leaderboard {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity + 1),
+            elf_count: 0,
+        }
+    }
+
+    pub fn push(&mut self, index: usize, total: i32) {
+        self.elf_count += 1;
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.heap.push(Reverse((total, Reverse(index))));
+        if self.heap.len() > self.capacity {
+            self.heap.pop();
+        }
+    }
+
+    pub fn elf_count(&self) -> usize {
+        self.elf_count
+    }
+
+    // Unlike `ranking`, this doesn't mind having fewer elves than requested.
+    pub fn standings(&self) -> Vec<ElfTotal> {
+        let mut entries = self.heap.clone().into_vec();
+        entries.sort();
+
+        entries
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| ElfTotal { index, total })
+            .collect()
+    }
+
+    pub fn ranking(&self) -> Result<Vec<ElfTotal>, LeaderboardError> {
+        if self.elf_count < self.capacity {
+            return Err(LeaderboardError::NotEnoughElves {
+                requested: self.capacity,
+                available: self.elf_count,
+            });
+        }
+
+        Ok(self.standings())
+    }
+}
+
+pub fn top_n_calorie_totals(
+    input: &str,
+    n: usize,
+    mode: ParseMode,
+) -> Result<Vec<ElfTotal>, LeaderboardError> {
+    let mut leaderboard = Leaderboard::new(n);
+    for (index, elf) in parse_inventories(input, mode)?.iter().enumerate() {
+        leaderboard.push(index, elf.total);
+    }
+    leaderboard.ranking()
+}
+
+// Only the running total of the current group and the leaderboard itself are
+// kept around, so memory usage does not depend on the size of the inventory.
+pub fn stream_top_n_calorie_totals<R: BufRead>(
+    mut reader: R,
+    n: usize,
+    mode: ParseMode,
+) -> Result<Vec<ElfTotal>, LeaderboardError> {
+    let mut leaderboard = Leaderboard::new(n);
+    let mut parser = GroupParser::new(mode);
+    let mut index = 0;
+    let mut line = Vec::new();
+
+    // Read as bytes so that a line that isn't UTF-8 is reported with its line
+    // number instead of as a bare read error.
+    while reader.read_until(b'\n', &mut line)? != 0 {
+        if let Some(elf) = parser.push_bytes(&line)? {
+            leaderboard.push(index, elf.total);
+            index += 1;
+        }
+        line.clear();
+    }
+    leaderboard.push(index, parser.finish()?.total);
+
+    leaderboard.ranking()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{stream_top_n_calorie_totals, top_n_calorie_totals, ElfTotal, LeaderboardError};
+    use crate::parsing::ParseMode;
+
+    const EXAMPLE_INPUT: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn top_1_is_the_elf_carrying_the_most_calories() {
+        let leaders = top_n_calorie_totals(EXAMPLE_INPUT, 1, ParseMode::Lenient).unwrap();
+
+        assert_eq!(
+            leaders,
+            vec![ElfTotal {
+                index: 3,
+                total: 24000
+            }]
+        );
+    }
+
+    #[test]
+    fn top_3_are_ranked_from_most_to_least_calories() {
+        let leaders = top_n_calorie_totals(EXAMPLE_INPUT, 3, ParseMode::Lenient).unwrap();
+
+        let indices: Vec<_> = leaders.iter().map(|elf| elf.index).collect();
+        let sum: i32 = leaders.iter().map(|elf| elf.total).sum();
+        assert_eq!(indices, vec![3, 2, 4]);
+        assert_eq!(sum, 45000);
+    }
+
+    #[test]
+    fn ties_are_ranked_by_position_in_the_file() {
+        let leaders = top_n_calorie_totals("5\n\n7\n\n5\n\n5", 3, ParseMode::Lenient).unwrap();
+
+        let indices: Vec<_> = leaders.iter().map(|elf| elf.index).collect();
+        assert_eq!(indices, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn asking_for_more_elves_than_available_is_an_error() {
+        let result = top_n_calorie_totals("100\n\n200", 3, ParseMode::Lenient);
+
+        assert!(matches!(
+            result,
+            Err(LeaderboardError::NotEnoughElves {
+                requested: 3,
+                available: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn streaming_from_a_reader_handles_crlf_line_endings() {
+        let reader = Cursor::new(EXAMPLE_INPUT.replace('\n', "\r\n"));
+
+        let leaders = stream_top_n_calorie_totals(reader, 3, ParseMode::Lenient).unwrap();
+
+        assert_eq!(
+            leaders,
+            top_n_calorie_totals(EXAMPLE_INPUT, 3, ParseMode::Lenient).unwrap()
+        );
+    }
+
+    #[test]
+    fn streaming_reports_invalid_utf8_with_its_line() {
+        let reader = Cursor::new(b"100\n\n2\xff0\n".to_vec());
+
+        let result = stream_top_n_calorie_totals(reader, 1, ParseMode::Lenient);
+
+        assert!(matches!(result, Err(LeaderboardError::Parse(err)) if err.line == 3));
+    }
+}
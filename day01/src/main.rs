@@ -1,23 +1,231 @@
-use std::{env, error::Error, fs};
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let path = &args[1];
-    let content = fs::read_to_string(path)?;
-
-    let mut sums = vec![0];
-    for line in content.lines() {
-        if line != "" {
-            *sums.last_mut().unwrap() += line.parse::<i32>()?;
-        } else {
-            sums.push(0);
-        }
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    process,
+    time::Duration,
+};
+
+use balancing::{balance_items, load_spread, BalanceMethod};
+use leaderboard::{stream_top_n_calorie_totals, top_n_calorie_totals, ElfTotal};
+use parsing::{format_inventories, parse_inventories, ParseMode};
+use report::{build_report, render_report, ReportFormat};
+use stats::{build_histogram, compute_statistics, render_histogram};
+use watch::{watch_file, InventoryFollower};
+
+mod balancing;
+mod leaderboard;
+mod parsing;
+mod report;
+mod stats;
+mod watch;
+
+const USAGE: &str = "\
+usage: day01 [top] <path|-> [--top N] [--stream] [--strict]
+       day01 report <path|-> [--format table|csv|json] [--strict]
+       day01 balance <path|-> --elves K [--method auto|exact|lpt|kk] [--strict]
+       day01 stats <path|-> [--buckets N] [--strict]
+       day01 watch <path> [--top N] [--interval MS] [--strict]";
+const DEFAULT_TOP: usize = 3;
+const DEFAULT_BUCKETS: usize = 10;
+const DEFAULT_INTERVAL_MS: u64 = 500;
+const STDIN_PATH: &str = "-";
+
+#[derive(PartialEq, Eq)]
+enum Command {
+    Top,
+    Report,
+    Balance,
+    Stats,
+    Watch,
+}
+
+struct Options {
+    command: Command,
+    path: String,
+    mode: ParseMode,
+    top: usize,
+    stream: bool,
+    format: ReportFormat,
+    elves: Option<usize>,
+    method: BalanceMethod,
+    buckets: usize,
+    interval: Duration,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
     }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_options(&args)?;
+
+    match options.command {
+        Command::Top => print_top(&options),
+        Command::Report => print_report(&options),
+        Command::Balance => print_balanced_inventory(&options),
+        Command::Stats => print_statistics(&options),
+        Command::Watch => watch_leaderboard(&options),
+    }
+}
 
-    sums.sort();
-    let last_3_index = sums.len() - 3;
-    let last_3 = &sums[last_3_index..];
+fn print_top(options: &Options) -> Result<(), Box<dyn Error>> {
+    let leaders = if options.stream {
+        stream_top_n_calorie_totals(open_input(&options.path)?, options.top, options.mode)?
+    } else {
+        let content = read_input(&options.path)?;
+        top_n_calorie_totals(&content, options.top, options.mode)?
+    };
 
-    print!("{:?}", last_3.iter().sum::<i32>());
+    for ElfTotal { index, total } in &leaders {
+        println!("elf {}: {}", index, total);
+    }
+
+    print!("{:?}", leaders.iter().map(|elf| elf.total).sum::<i32>());
     Ok(())
 }
+
+fn print_report(options: &Options) -> Result<(), Box<dyn Error>> {
+    let content = read_input(&options.path)?;
+    let inventories = parse_inventories(&content, options.mode)?;
+    let rows = build_report(&inventories);
+
+    print!("{}", render_report(&rows, options.format));
+    Ok(())
+}
+
+fn print_balanced_inventory(options: &Options) -> Result<(), Box<dyn Error>> {
+    let elf_count = options
+        .elves
+        .ok_or("balance needs the number of elves (--elves K)")?;
+    let content = read_input(&options.path)?;
+    let items: Vec<_> = parse_inventories(&content, options.mode)?
+        .into_iter()
+        .flat_map(|elf| elf.items)
+        .collect();
+
+    let elves = balance_items(&items, elf_count, options.method)?;
+    let (max, min) = load_spread(&elves);
+
+    print!("{}", format_inventories(&elves));
+    eprintln!(
+        "max load: {}, min load: {}, spread: {}",
+        max,
+        min,
+        max - min
+    );
+    Ok(())
+}
+
+fn print_statistics(options: &Options) -> Result<(), Box<dyn Error>> {
+    let content = read_input(&options.path)?;
+    let totals: Vec<_> = parse_inventories(&content, options.mode)?
+        .iter()
+        .map(|elf| elf.total)
+        .collect();
+
+    let stats = compute_statistics(&totals).ok_or("the inventory has no elves")?;
+    let histogram = build_histogram(&totals, options.buckets);
+
+    print!("{}\n{}", stats, render_histogram(&histogram));
+    Ok(())
+}
+
+fn watch_leaderboard(options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut follower = InventoryFollower::new(options.top, options.mode);
+
+    watch_file(&options.path, &mut follower, options.interval, |follower| {
+        println!("{} elves", follower.elf_count());
+        for ElfTotal { index, total } in follower.standings() {
+            println!("elf {}: {}", index, total);
+        }
+        println!();
+    })?;
+    Ok(())
+}
+
+fn parse_options(args: &[String]) -> Result<Options, Box<dyn Error>> {
+    let mut args = args.iter().peekable();
+    let command = match args.peek().map(|arg| arg.as_str()) {
+        Some("top") => {
+            args.next();
+            Command::Top
+        }
+        Some("report") => {
+            args.next();
+            Command::Report
+        }
+        Some("balance") => {
+            args.next();
+            Command::Balance
+        }
+        Some("stats") => {
+            args.next();
+            Command::Stats
+        }
+        Some("watch") => {
+            args.next();
+            Command::Watch
+        }
+        _ => Command::Top,
+    };
+
+    let path = args.next().ok_or(USAGE)?.clone();
+    let mut options = Options {
+        command,
+        stream: path == STDIN_PATH,
+        path,
+        mode: ParseMode::Lenient,
+        top: DEFAULT_TOP,
+        format: ReportFormat::Table,
+        elves: None,
+        method: BalanceMethod::Auto,
+        buckets: DEFAULT_BUCKETS,
+        interval: Duration::from_millis(DEFAULT_INTERVAL_MS),
+    };
+
+    while let Some(arg) = args.next() {
+        match (&options.command, arg.as_str()) {
+            (_, "--strict") => options.mode = ParseMode::Strict,
+            (Command::Top | Command::Watch, "--top") => {
+                options.top = args.next().ok_or(USAGE)?.parse()?
+            }
+            (Command::Top, "--stream") => options.stream = true,
+            (Command::Report, "--format") => options.format = args.next().ok_or(USAGE)?.parse()?,
+            (Command::Balance, "--elves") => {
+                options.elves = Some(args.next().ok_or(USAGE)?.parse()?)
+            }
+            (Command::Balance, "--method") => options.method = args.next().ok_or(USAGE)?.parse()?,
+            (Command::Stats, "--buckets") => options.buckets = args.next().ok_or(USAGE)?.parse()?,
+            (Command::Watch, "--interval") => {
+                options.interval = Duration::from_millis(args.next().ok_or(USAGE)?.parse()?)
+            }
+            _ => return Err(format!("unexpected argument: {}\n{}", arg, USAGE).into()),
+        }
+    }
+
+    Ok(options)
+}
+
+fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == STDIN_PATH {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == STDIN_PATH {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        Ok(content)
+    } else {
+        fs::read_to_string(path)
+    }
+}
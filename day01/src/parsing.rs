@@ -0,0 +1,345 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    Lenient,
+    Strict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCalories(ParseIntError),
+    EmptyGroup,
+    NegativeCalories,
+    GroupTotalOverflow,
+    InvalidUtf8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for InventoryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::InvalidCalories(err) => {
+                write!(f, "invalid calorie count {:?} ({})", self.text, err)
+            }
+            ParseErrorKind::EmptyGroup => write!(f, "elf without any item"),
+            ParseErrorKind::NegativeCalories => {
+                write!(f, "negative calorie count {:?}", self.text)
+            }
+            ParseErrorKind::GroupTotalOverflow => write!(
+                f,
+                "adding {:?} overflows the calorie total of the elf",
+                self.text
+            ),
+            ParseErrorKind::InvalidUtf8 => write!(f, "{:?} is not valid UTF-8", self.text),
+        }
+    }
+}
+
+impl Error for InventoryParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfInventory {
+    pub items: Vec<i32>,
+    pub total: i32,
+}
+
+// Inventories are fed one line at a time so that the same parser can be used
+// on a whole file or on a stream that never fits in memory.
+pub struct GroupParser {
+    mode: ParseMode,
+    // When off, groups come out with their total only and memory usage stays
+    // constant however large a group gets.
+    keep_items: bool,
+    line_number: usize,
+    group: ElfInventory,
+    group_item_count: usize,
+    group_separator_line: usize,
+}
+
+impl GroupParser {
+    pub fn new(mode: ParseMode) -> GroupParser {
+        GroupParser {
+            mode,
+            keep_items: false,
+            line_number: 0,
+            group: ElfInventory {
+                items: vec![],
+                total: 0,
+            },
+            group_item_count: 0,
+            group_separator_line: 1,
+        }
+    }
+
+    pub fn keeping_items(mode: ParseMode) -> GroupParser {
+        GroupParser {
+            keep_items: true,
+            ..GroupParser::new(mode)
+        }
+    }
+
+    // Returns the group completed by this line, if any.
+    pub fn push_line(&mut self, line: &str) -> Result<Option<ElfInventory>, InventoryParseError> {
+        self.line_number += 1;
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        if line.is_empty() {
+            if self.mode == ParseMode::Strict && self.group_item_count == 0 {
+                return Err(self.error(1, line, ParseErrorKind::EmptyGroup));
+            }
+            self.group_separator_line = self.line_number;
+            return Ok(Some(self.take_group()));
+        }
+
+        let calories = line.parse::<i32>().map_err(|err| {
+            self.error(
+                invalid_column(line),
+                line,
+                ParseErrorKind::InvalidCalories(err),
+            )
+        })?;
+        if self.mode == ParseMode::Strict && calories < 0 {
+            return Err(self.error(1, line, ParseErrorKind::NegativeCalories));
+        }
+
+        self.group.total = self
+            .group
+            .total
+            .checked_add(calories)
+            .ok_or_else(|| self.error(1, line, ParseErrorKind::GroupTotalOverflow))?;
+        self.group_item_count += 1;
+        if self.keep_items {
+            self.group.items.push(calories);
+        }
+
+        Ok(None)
+    }
+
+    // Same as `push_line` for a line read as bytes, which a stream doesn't
+    // guarantee to be UTF-8.
+    pub fn push_bytes(&mut self, line: &[u8]) -> Result<Option<ElfInventory>, InventoryParseError> {
+        match std::str::from_utf8(line) {
+            Ok(line) => self.push_line(line),
+            Err(err) => {
+                self.line_number += 1;
+                let valid = std::str::from_utf8(&line[..err.valid_up_to()]).unwrap();
+                let text = String::from_utf8_lossy(line);
+                Err(self.error(
+                    valid.chars().count() + 1,
+                    text.trim_end_matches(['\n', '\r']),
+                    ParseErrorKind::InvalidUtf8,
+                ))
+            }
+        }
+    }
+
+    // Returns the last group, which is not followed by a blank line.
+    pub fn finish(mut self) -> Result<ElfInventory, InventoryParseError> {
+        if self.mode == ParseMode::Strict && self.group_item_count == 0 {
+            self.line_number = self.group_separator_line;
+            return Err(self.error(1, "", ParseErrorKind::EmptyGroup));
+        }
+        Ok(self.take_group())
+    }
+
+    fn take_group(&mut self) -> ElfInventory {
+        self.group_item_count = 0;
+        ElfInventory {
+            items: std::mem::take(&mut self.group.items),
+            total: std::mem::take(&mut self.group.total),
+        }
+    }
+
+    fn error(&self, column: usize, text: &str, kind: ParseErrorKind) -> InventoryParseError {
+        InventoryParseError {
+            line: self.line_number,
+            column,
+            text: text.to_string(),
+            kind,
+        }
+    }
+}
+
+pub fn parse_inventories(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Vec<ElfInventory>, InventoryParseError> {
+    let mut parser = GroupParser::keeping_items(mode);
+    let mut inventories = vec![];
+    for line in input.lines() {
+        inventories.extend(parser.push_line(line)?);
+    }
+    inventories.push(parser.finish()?);
+    Ok(inventories)
+}
+
+pub fn format_inventories(inventories: &[ElfInventory]) -> String {
+    inventories
+        .iter()
+        .map(|elf| {
+            elf.items
+                .iter()
+                .map(|item| format!("{}\n", item))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Points at the first character that can't be part of an integer, or at the
+// start of the line when the number is well-formed but out of range.
+fn invalid_column(line: &str) -> usize {
+    let unsigned = line.strip_prefix(['-', '+']).unwrap_or(line);
+    let sign_len = line.len() - unsigned.len();
+
+    match unsigned.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, _)) => line[..sign_len + i].chars().count() + 1,
+        None if unsigned.is_empty() => sign_len + 1,
+        None => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_inventories, parse_inventories, ElfInventory, GroupParser, InventoryParseError,
+        ParseErrorKind, ParseMode,
+    };
+
+    fn parse_calorie_totals(input: &str, mode: ParseMode) -> Result<Vec<i32>, InventoryParseError> {
+        parse_inventories(input, mode)
+            .map(|inventories| inventories.iter().map(|elf| elf.total).collect())
+    }
+
+    #[test]
+    fn individual_items_are_kept_for_each_elf() {
+        let inventories = parse_inventories(
+            "1000
+2000
+
+4000",
+            ParseMode::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            inventories,
+            vec![
+                ElfInventory {
+                    items: vec![1000, 2000],
+                    total: 3000
+                },
+                ElfInventory {
+                    items: vec![4000],
+                    total: 4000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn streaming_parser_only_keeps_totals() {
+        let mut parser = GroupParser::new(ParseMode::Strict);
+
+        assert_eq!(parser.push_line("1000").unwrap(), None);
+        assert_eq!(parser.push_line("2000").unwrap(), None);
+        let elf = parser.push_line("").unwrap().unwrap();
+        assert_eq!(elf.total, 3000);
+        assert!(elf.items.is_empty());
+        assert!(matches!(
+            parser.push_line("").unwrap_err().kind,
+            ParseErrorKind::EmptyGroup
+        ));
+    }
+
+    #[test]
+    fn invalid_utf8_is_reported_on_its_line() {
+        let mut parser = GroupParser::new(ParseMode::Strict);
+
+        assert_eq!(parser.push_bytes(b"1000\n").unwrap(), None);
+        let err = parser.push_bytes(b"2\xff00\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.text, "2\u{fffd}00");
+        assert_eq!(err.kind, ParseErrorKind::InvalidUtf8);
+    }
+
+    #[test]
+    fn totals_are_summed_per_blank_line_separated_group() {
+        let totals = parse_calorie_totals("1000\n2000\n\n4000\n", ParseMode::Strict).unwrap();
+
+        assert_eq!(totals, vec![3000, 4000]);
+    }
+
+    #[test]
+    fn invalid_calories_are_reported_with_line_column_and_text() {
+        let err = parse_calorie_totals("1000\n\n20x0\n", ParseMode::Lenient).unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.text, "20x0");
+        assert!(matches!(err.kind, ParseErrorKind::InvalidCalories(_)));
+    }
+
+    #[test]
+    fn repeated_blank_lines_create_empty_elves_in_lenient_mode() {
+        let totals = parse_calorie_totals("1000\n\n\n2000", ParseMode::Lenient).unwrap();
+
+        assert_eq!(totals, vec![1000, 0, 2000]);
+    }
+
+    #[test]
+    fn repeated_blank_lines_are_rejected_in_strict_mode() {
+        let err = parse_calorie_totals("1000\n\n\n2000", ParseMode::Strict).unwrap_err();
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.kind, ParseErrorKind::EmptyGroup);
+    }
+
+    #[test]
+    fn trailing_blank_line_is_rejected_in_strict_mode() {
+        let err = parse_calorie_totals("1000\n\n", ParseMode::Strict).unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.kind, ParseErrorKind::EmptyGroup);
+    }
+
+    #[test]
+    fn negative_calories_are_rejected_in_strict_mode() {
+        let lenient = parse_calorie_totals("1000\n-200", ParseMode::Lenient).unwrap();
+        let err = parse_calorie_totals("1000\n-200", ParseMode::Strict).unwrap_err();
+
+        assert_eq!(lenient, vec![800]);
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.kind, ParseErrorKind::NegativeCalories);
+    }
+
+    #[test]
+    fn group_total_overflow_is_reported_on_the_offending_line() {
+        let err = parse_calorie_totals("2147483647\n1", ParseMode::Strict).unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "1");
+        assert_eq!(err.kind, ParseErrorKind::GroupTotalOverflow);
+    }
+
+    #[test]
+    fn formatted_inventories_can_be_parsed_back() {
+        let input = "1000\n2000\n\n4000\n";
+
+        let inventories = parse_inventories(input, ParseMode::Strict).unwrap();
+
+        assert_eq!(format_inventories(&inventories), input);
+    }
+}
@@ -0,0 +1,189 @@
+use std::{cmp::Reverse, fmt::Write, str::FromStr};
+
+use crate::parsing::ElfInventory;
+
+const HEADERS: [&str; 5] = ["index", "item_count", "total", "largest_item", "rank"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("{} is not a valid report format", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfReportRow {
+    pub index: usize,
+    pub item_count: usize,
+    pub total: i32,
+    pub largest_item: Option<i32>,
+    pub rank: usize,
+}
+
+// Rows stay in file order. Ranks are unique and follow the leaderboard:
+// most calories first, ties going to the elf that comes first in the file.
+pub fn build_report(inventories: &[ElfInventory]) -> Vec<ElfReportRow> {
+    let mut by_rank: Vec<usize> = (0..inventories.len()).collect();
+    by_rank.sort_by_key(|&index| (Reverse(inventories[index].total), index));
+
+    let mut ranks = vec![0; inventories.len()];
+    for (rank, index) in by_rank.into_iter().enumerate() {
+        ranks[index] = rank + 1;
+    }
+
+    inventories
+        .iter()
+        .enumerate()
+        .map(|(index, elf)| ElfReportRow {
+            index,
+            item_count: elf.items.len(),
+            total: elf.total,
+            largest_item: elf.items.iter().max().copied(),
+            rank: ranks[index],
+        })
+        .collect()
+}
+
+pub fn render_report(rows: &[ElfReportRow], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Table => render_table(rows),
+        ReportFormat::Csv => render_csv(rows),
+        ReportFormat::Json => render_json(rows),
+    }
+}
+
+fn cells(row: &ElfReportRow) -> [String; 5] {
+    [
+        row.index.to_string(),
+        row.item_count.to_string(),
+        row.total.to_string(),
+        row.largest_item.map(|n| n.to_string()).unwrap_or_default(),
+        row.rank.to_string(),
+    ]
+}
+
+fn render_table(rows: &[ElfReportRow]) -> String {
+    let rows: Vec<_> = rows.iter().map(cells).collect();
+    let widths: Vec<_> = HEADERS
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([header.len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut table = String::new();
+    let header = HEADERS.map(String::from);
+    for row in [header].iter().chain(rows.iter()) {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        writeln!(table, "{}", line.join("  ")).unwrap();
+    }
+    table
+}
+
+fn render_csv(rows: &[ElfReportRow]) -> String {
+    let mut csv = format!("{}\n", HEADERS.join(","));
+    for row in rows {
+        writeln!(csv, "{}", cells(row).join(",")).unwrap();
+    }
+    csv
+}
+
+fn render_json(rows: &[ElfReportRow]) -> String {
+    let objects: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            format!(
+                "  {{\"index\": {}, \"item_count\": {}, \"total\": {}, \"largest_item\": {}, \"rank\": {}}}",
+                row.index,
+                row.item_count,
+                row.total,
+                row.largest_item
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "null".to_string()),
+                row.rank
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_report, render_report, ElfReportRow, ReportFormat};
+    use crate::parsing::{parse_inventories, ParseMode};
+
+    const EXAMPLE_INPUT: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn report_keeps_file_order_and_ranks_by_total() {
+        let inventories = parse_inventories(EXAMPLE_INPUT, ParseMode::Strict).unwrap();
+
+        let rows = build_report(&inventories);
+
+        let ranks: Vec<_> = rows.iter().map(|row| row.rank).collect();
+        assert_eq!(ranks, vec![4, 5, 2, 1, 3]);
+        assert_eq!(
+            rows[3],
+            ElfReportRow {
+                index: 3,
+                item_count: 3,
+                total: 24000,
+                largest_item: Some(9000),
+                rank: 1
+            }
+        );
+    }
+
+    #[test]
+    fn csv_report_has_a_header_and_one_line_per_elf() {
+        let inventories = parse_inventories("1\n2\n\n\n5", ParseMode::Lenient).unwrap();
+
+        let csv = render_report(&build_report(&inventories), ReportFormat::Csv);
+
+        assert_eq!(
+            csv,
+            "index,item_count,total,largest_item,rank\n0,2,3,2,2\n1,0,0,,3\n2,1,5,5,1\n"
+        );
+    }
+
+    #[test]
+    fn json_report_uses_null_for_elves_without_items() {
+        let inventories = parse_inventories("3\n\n", ParseMode::Lenient).unwrap();
+
+        let json = render_report(&build_report(&inventories), ReportFormat::Json);
+
+        assert_eq!(
+            json,
+            "[\n  \
+            {\"index\": 0, \"item_count\": 1, \"total\": 3, \"largest_item\": 3, \"rank\": 1},\n  \
+            {\"index\": 1, \"item_count\": 0, \"total\": 0, \"largest_item\": null, \"rank\": 2}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::{self, Display};
+
+const HISTOGRAM_WIDTH: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotalStatistics {
+    pub count: usize,
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    pub median: f64,
+    pub p50: i32,
+    pub p90: i32,
+    pub p99: i32,
+    pub standard_deviation: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistogramBucket {
+    pub start: i64,
+    pub end: i64,
+    pub count: usize,
+}
+
+pub fn compute_statistics(totals: &[i32]) -> Option<TotalStatistics> {
+    if totals.is_empty() {
+        return None;
+    }
+
+    let mut sorted = totals.to_vec();
+    sorted.sort();
+    let count = sorted.len();
+    let mean = sorted.iter().map(|&total| total as f64).sum::<f64>() / count as f64;
+    let variance = sorted
+        .iter()
+        .map(|&total| (total as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count as f64;
+    let median = if count.is_multiple_of(2) {
+        (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+    } else {
+        sorted[count / 2] as f64
+    };
+
+    Some(TotalStatistics {
+        count,
+        min: sorted[0],
+        max: sorted[count - 1],
+        mean,
+        median,
+        p50: percentile(&sorted, 50),
+        p90: percentile(&sorted, 90),
+        p99: percentile(&sorted, 99),
+        standard_deviation: variance.sqrt(),
+    })
+}
+
+// Nearest-rank percentile, so the result is always one of the actual totals.
+fn percentile(sorted: &[i32], p: usize) -> i32 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+// Buckets share the same width and cover every total from min to max, the
+// last one including max itself.
+pub fn build_histogram(totals: &[i32], bucket_count: usize) -> Vec<HistogramBucket> {
+    let (Some(&min), Some(&max)) = (totals.iter().min(), totals.iter().max()) else {
+        return vec![];
+    };
+    let bucket_count = bucket_count.max(1);
+    let span = max as i64 - min as i64 + 1;
+    let width = (span + bucket_count as i64 - 1) / bucket_count as i64;
+
+    let mut buckets: Vec<_> = (0..bucket_count as i64)
+        .map(|i| HistogramBucket {
+            start: min as i64 + i * width,
+            end: (min as i64 + (i + 1) * width - 1).min(max as i64),
+            count: 0,
+        })
+        .take_while(|bucket| bucket.start <= max as i64)
+        .collect();
+    for &total in totals {
+        let i = (total as i64 - min as i64) / width;
+        buckets[i as usize].count += 1;
+    }
+
+    buckets
+}
+
+pub fn render_histogram(buckets: &[HistogramBucket]) -> String {
+    let largest = buckets.iter().map(|bucket| bucket.count).max().unwrap_or(0);
+    let labels: Vec<_> = buckets
+        .iter()
+        .map(|bucket| format!("{}..={}", bucket.start, bucket.end))
+        .collect();
+    let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+
+    buckets
+        .iter()
+        .zip(labels)
+        .map(|(bucket, label)| {
+            // Non-empty buckets always get at least one mark so they stand out.
+            let bar_len = match bucket.count {
+                0 => 0,
+                count => (count * HISTOGRAM_WIDTH / largest).max(1),
+            };
+            format!(
+                "{:>width$} | {} {}\n",
+                label,
+                "#".repeat(bar_len),
+                bucket.count,
+                width = label_width
+            )
+        })
+        .collect()
+}
+
+impl Display for TotalStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:  {}", self.count)?;
+        writeln!(f, "min:    {}", self.min)?;
+        writeln!(f, "max:    {}", self.max)?;
+        writeln!(f, "mean:   {:.2}", self.mean)?;
+        writeln!(f, "median: {:.1}", self.median)?;
+        writeln!(f, "p50:    {}", self.p50)?;
+        writeln!(f, "p90:    {}", self.p90)?;
+        writeln!(f, "p99:    {}", self.p99)?;
+        writeln!(f, "stddev: {:.2}", self.standard_deviation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_histogram, compute_statistics, render_histogram, HistogramBucket};
+
+    const EXAMPLE_TOTALS: [i32; 5] = [6000, 4000, 11000, 24000, 10000];
+
+    #[test]
+    fn statistics_of_the_example_totals() {
+        let stats = compute_statistics(&EXAMPLE_TOTALS).unwrap();
+
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!((stats.p50, stats.p90, stats.p99), (10000, 24000, 24000));
+        assert!((stats.standard_deviation - 6985.70).abs() < 0.01);
+    }
+
+    #[test]
+    fn median_of_an_even_count_is_the_mean_of_the_middle_totals() {
+        let stats = compute_statistics(&[1, 2, 3, 10]).unwrap();
+
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.p50, 2);
+    }
+
+    #[test]
+    fn no_statistics_without_elves() {
+        assert_eq!(compute_statistics(&[]), None);
+    }
+
+    #[test]
+    fn histogram_buckets_cover_every_total() {
+        let buckets = build_histogram(&[0, 1, 5, 9, 9], 2);
+
+        assert_eq!(
+            buckets,
+            vec![
+                HistogramBucket {
+                    start: 0,
+                    end: 4,
+                    count: 2
+                },
+                HistogramBucket {
+                    start: 5,
+                    end: 9,
+                    count: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn histogram_bars_are_scaled_to_the_largest_bucket() {
+        let buckets = build_histogram(&[0, 1, 5, 9, 9], 2);
+
+        let histogram = render_histogram(&buckets);
+
+        assert_eq!(
+            histogram,
+            format!(
+                "0..=4 | {} 2\n5..=9 | {} 3\n",
+                "#".repeat(33),
+                "#".repeat(50)
+            )
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    leaderboard::{ElfTotal, Leaderboard, LeaderboardError},
+    parsing::{GroupParser, InventoryParseError, ParseMode},
+};
+
+// Keeps the leaderboard up to date as text is appended to the inventory. Only
+// groups closed by a blank line are ranked, since the last one may still grow.
+pub struct InventoryFollower {
+    top: usize,
+    mode: ParseMode,
+    parser: GroupParser,
+    leaderboard: Leaderboard,
+    partial_line: Vec<u8>,
+}
+
+impl InventoryFollower {
+    pub fn new(top: usize, mode: ParseMode) -> InventoryFollower {
+        InventoryFollower {
+            top,
+            mode,
+            parser: GroupParser::new(mode),
+            leaderboard: Leaderboard::new(top),
+            partial_line: vec![],
+        }
+    }
+
+    // Returns how many groups were completed by the appended bytes.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<usize, InventoryParseError> {
+        let mut completed = 0;
+        self.partial_line.extend_from_slice(bytes);
+
+        while let Some(end) = self.partial_line.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.partial_line.drain(..=end).collect();
+            if let Some(elf) = self.parser.push_bytes(&line)? {
+                let index = self.leaderboard.elf_count();
+                self.leaderboard.push(index, elf.total);
+                completed += 1;
+            }
+        }
+
+        Ok(completed)
+    }
+
+    pub fn reset(&mut self) {
+        *self = InventoryFollower::new(self.top, self.mode);
+    }
+
+    pub fn elf_count(&self) -> usize {
+        self.leaderboard.elf_count()
+    }
+
+    pub fn standings(&self) -> Vec<ElfTotal> {
+        self.leaderboard.standings()
+    }
+}
+
+// Polls the file forever, only reading what was appended since the last poll.
+// A file that shrinks is assumed to have been truncated and is read again.
+pub fn watch_file<F>(
+    path: &str,
+    follower: &mut InventoryFollower,
+    interval: Duration,
+    mut on_update: F,
+) -> Result<(), LeaderboardError>
+where
+    F: FnMut(&InventoryFollower),
+{
+    let mut file = File::open(path)?;
+    let mut offset = 0;
+    let mut bytes = vec![];
+
+    loop {
+        let len = file.metadata()?.len();
+        if len < offset {
+            follower.reset();
+            offset = 0;
+        }
+
+        if len > offset {
+            file.seek(SeekFrom::Start(offset))?;
+            bytes.clear();
+            offset += file.read_to_end(&mut bytes)? as u64;
+
+            if follower.feed(&bytes)? > 0 {
+                on_update(follower);
+            }
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InventoryFollower;
+    use crate::{leaderboard::ElfTotal, parsing::ParseMode};
+
+    #[test]
+    fn only_groups_closed_by_a_blank_line_are_ranked() {
+        let mut follower = InventoryFollower::new(2, ParseMode::Lenient);
+
+        let completed = follower.feed(b"1000\n2000\n\n4000\n").unwrap();
+
+        assert_eq!(completed, 1);
+        assert_eq!(
+            follower.standings(),
+            vec![ElfTotal {
+                index: 0,
+                total: 3000
+            }]
+        );
+    }
+
+    #[test]
+    fn lines_split_across_appends_are_joined() {
+        let mut follower = InventoryFollower::new(2, ParseMode::Lenient);
+
+        follower.feed(b"1000\n20").unwrap();
+        follower.feed(b"00\n").unwrap();
+        let completed = follower.feed(b"\n5000\n\n").unwrap();
+
+        assert_eq!(completed, 2);
+        assert_eq!(
+            follower.standings(),
+            vec![
+                ElfTotal {
+                    index: 1,
+                    total: 5000
+                },
+                ElfTotal {
+                    index: 0,
+                    total: 3000
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn leaderboard_is_bounded_as_elves_keep_coming() {
+        let mut follower = InventoryFollower::new(1, ParseMode::Lenient);
+
+        follower.feed(b"1\n\n3\n\n2\n\n").unwrap();
+
+        assert_eq!(follower.elf_count(), 3);
+        assert_eq!(follower.standings(), vec![ElfTotal { index: 1, total: 3 }]);
+    }
+
+    #[test]
+    fn reset_forgets_every_elf() {
+        let mut follower = InventoryFollower::new(1, ParseMode::Lenient);
+        follower.feed(b"1\n\n").unwrap();
+
+        follower.reset();
+
+        assert_eq!(follower.elf_count(), 0);
+        assert_eq!(follower.standings(), vec![]);
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt::Write;
+
+use crate::{
+    game::{Game, Outcome},
+    Move, Round,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundExplanation {
+    pub opponent_move: Move,
+    pub my_move: Move,
+    pub outcome: Outcome,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+}
+
+impl RoundExplanation {
+    pub fn score(&self) -> i32 {
+        self.shape_points + self.outcome_points
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveBreakdown {
+    pub my_move: Move,
+    pub rounds: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    pub total: i32,
+    pub per_move: Vec<MoveBreakdown>,
+}
+
+// Rounds are scored with `Game::rock_paper_scissors`, built once by the caller.
+pub fn explain_round(game: &Game, round: &Round) -> RoundExplanation {
+    let me = round.my_move.shape_index();
+    let outcome = game.outcome(me, round.opponent_move.shape_index());
+
+    RoundExplanation {
+        opponent_move: round.opponent_move.clone(),
+        my_move: round.my_move.clone(),
+        outcome,
+        shape_points: game.shape_points(me),
+        outcome_points: game.outcome_points(outcome),
+    }
+}
+
+pub fn break_down_score(game: &Game, rounds: &[Round]) -> ScoreBreakdown {
+    let mut per_move: Vec<_> = Move::ALL
+        .iter()
+        .map(|my_move| MoveBreakdown {
+            my_move: my_move.clone(),
+            rounds: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            shape_points: 0,
+            outcome_points: 0,
+        })
+        .collect();
+
+    for explanation in rounds.iter().map(|round| explain_round(game, round)) {
+        let breakdown = &mut per_move[explanation.my_move.shape_index()];
+        breakdown.rounds += 1;
+        match explanation.outcome {
+            Outcome::Win => breakdown.wins += 1,
+            Outcome::Draw => breakdown.draws += 1,
+            Outcome::Loss => breakdown.losses += 1,
+        }
+        breakdown.shape_points += explanation.shape_points;
+        breakdown.outcome_points += explanation.outcome_points;
+    }
+
+    ScoreBreakdown {
+        total: per_move
+            .iter()
+            .map(|breakdown| breakdown.shape_points + breakdown.outcome_points)
+            .sum(),
+        per_move,
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Win => "win",
+        Outcome::Draw => "draw",
+        Outcome::Loss => "loss",
+    }
+}
+
+pub fn render_explanation(explanation: &RoundExplanation) -> String {
+    format!(
+        "opponent {:?}, me {:?}, {}: {} + {} = {}",
+        explanation.opponent_move,
+        explanation.my_move,
+        outcome_name(explanation.outcome),
+        explanation.shape_points,
+        explanation.outcome_points,
+        explanation.score()
+    )
+}
+
+pub fn render_breakdown(breakdown: &ScoreBreakdown) -> String {
+    let mut table = format!(
+        "{:<8}  {:>6}  {:>4}  {:>5}  {:>6}  {:>6}  {:>7}\n",
+        "move", "rounds", "wins", "draws", "losses", "shape", "outcome"
+    );
+    for b in &breakdown.per_move {
+        writeln!(
+            table,
+            "{:<8}  {:>6}  {:>4}  {:>5}  {:>6}  {:>6}  {:>7}",
+            format!("{:?}", b.my_move),
+            b.rounds,
+            b.wins,
+            b.draws,
+            b.losses,
+            b.shape_points,
+            b.outcome_points
+        )
+        .unwrap();
+    }
+    writeln!(table, "total: {}", breakdown.total).unwrap();
+    table
+}
+
+// Rounds are only listed when explanations are given.
+pub fn render_breakdown_json(
+    breakdown: &ScoreBreakdown,
+    explanations: Option<&[RoundExplanation]>,
+) -> String {
+    let moves: Vec<_> = breakdown
+        .per_move
+        .iter()
+        .map(|b| {
+            format!(
+                "{{\"move\": \"{:?}\", \"rounds\": {}, \"wins\": {}, \"draws\": {}, \"losses\": {}, \"shape_points\": {}, \"outcome_points\": {}}}",
+                b.my_move, b.rounds, b.wins, b.draws, b.losses, b.shape_points, b.outcome_points
+            )
+        })
+        .collect();
+
+    let mut json = format!(
+        "{{\"total\": {}, \"moves\": [{}]",
+        breakdown.total,
+        moves.join(", ")
+    );
+    if let Some(explanations) = explanations {
+        let rounds: Vec<_> = explanations
+            .iter()
+            .map(|e| {
+                format!(
+                    "{{\"opponent\": \"{:?}\", \"me\": \"{:?}\", \"outcome\": \"{}\", \"shape_points\": {}, \"outcome_points\": {}, \"score\": {}}}",
+                    e.opponent_move,
+                    e.my_move,
+                    outcome_name(e.outcome),
+                    e.shape_points,
+                    e.outcome_points,
+                    e.score()
+                )
+            })
+            .collect();
+        write!(json, ", \"rounds\": [{}]", rounds.join(", ")).unwrap();
+    }
+    json.push_str("}\n");
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        break_down_score, explain_round, render_breakdown_json, render_explanation, MoveBreakdown,
+    };
+    use crate::{
+        game::Game,
+        parsing::{parse_rounds, Notation, ParseMode, StrategyInterpretation},
+        Move, Round,
+    };
+
+    fn example_rounds() -> Vec<Round> {
+        parse_rounds(
+            "A Y\nB X\nC Z",
+            StrategyInterpretation::Shape,
+            ParseMode::Strict,
+            Notation::Letters,
+        )
+        .unwrap()
+        .rounds
+    }
+
+    #[test]
+    fn rounds_are_explained_as_shape_plus_outcome_points() {
+        let game = Game::rock_paper_scissors();
+        let explanations: Vec<_> = example_rounds()
+            .iter()
+            .map(|round| render_explanation(&explain_round(&game, round)))
+            .collect();
+
+        assert_eq!(
+            explanations,
+            vec![
+                "opponent Rock, me Paper, win: 2 + 6 = 8",
+                "opponent Paper, me Rock, loss: 1 + 0 = 1",
+                "opponent Scissors, me Scissors, draw: 3 + 3 = 6",
+            ]
+        );
+    }
+
+    #[test]
+    fn breakdown_sums_up_to_the_total_score() {
+        let breakdown = break_down_score(&Game::rock_paper_scissors(), &example_rounds());
+
+        assert_eq!(breakdown.total, 15);
+        assert_eq!(
+            breakdown.per_move[1],
+            MoveBreakdown {
+                my_move: Move::Paper,
+                rounds: 1,
+                wins: 1,
+                draws: 0,
+                losses: 0,
+                shape_points: 2,
+                outcome_points: 6
+            }
+        );
+    }
+
+    #[test]
+    fn json_lists_rounds_only_when_explained() {
+        let rounds = example_rounds();
+        let game = Game::rock_paper_scissors();
+        let breakdown = break_down_score(&game, &rounds[..1]);
+        let explanations = [explain_round(&game, &rounds[0])];
+
+        let without = render_breakdown_json(&breakdown, None);
+        let with = render_breakdown_json(&breakdown, Some(&explanations));
+
+        assert!(!without.contains("\"rounds\": ["));
+        assert!(with.ends_with(
+            ", \"rounds\": [{\"opponent\": \"Rock\", \"me\": \"Paper\", \"outcome\": \"win\", \"shape_points\": 2, \"outcome_points\": 6, \"score\": 8}]}\n"
+        ));
+    }
+}
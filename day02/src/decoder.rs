@@ -0,0 +1,193 @@
+use std::fmt::{self, Display, Write};
+
+use crate::{
+    calculate_score,
+    game::Outcome,
+    move_for_outcome,
+    parsing::{parse_move, split_columns, ParseMode, RoundParseError, RoundParseErrorKind},
+    Move, Round,
+};
+
+const CODES: [&str; 3] = ["X", "Y", "Z"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes([Move; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let targets: Vec<String> = match self {
+            Mapping::Shapes(moves) => moves.iter().map(|m| format!("{:?}", m)).collect(),
+            Mapping::Outcomes(outcomes) => outcomes.iter().map(|o| format!("{:?}", o)).collect(),
+        };
+        let pairs: Vec<_> = CODES
+            .iter()
+            .zip(targets)
+            .map(|(code, target)| format!("{}={}", code, target))
+            .collect();
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedReading {
+    pub mapping: Mapping,
+    pub score: i32,
+}
+
+// Scores the guide under every way X/Y/Z could map to a shape or to an
+// outcome, best reading first.
+pub fn decode_strategy_guide(
+    s: &str,
+    mode: ParseMode,
+) -> Result<Vec<DecodedReading>, RoundParseError> {
+    let mut columns = vec![];
+    for (i, line) in s.lines().enumerate() {
+        columns.extend(parse_columns(i + 1, line, mode)?);
+    }
+
+    let shape_mappings = permutations(&[Move::Rock, Move::Paper, Move::Scissors])
+        .into_iter()
+        .map(|moves| Mapping::Shapes([moves[0].clone(), moves[1].clone(), moves[2].clone()]));
+    let outcome_mappings = permutations(&[Outcome::Loss, Outcome::Draw, Outcome::Win])
+        .into_iter()
+        .map(|outcomes| Mapping::Outcomes([outcomes[0], outcomes[1], outcomes[2]]));
+
+    let mut readings: Vec<_> = shape_mappings
+        .chain(outcome_mappings)
+        .map(|mapping| DecodedReading {
+            score: calculate_score(&apply_mapping(&columns, &mapping)),
+            mapping,
+        })
+        .collect();
+    // The sort is stable, so readings with the same score keep a fixed order.
+    readings.sort_by_key(|reading| -reading.score);
+    Ok(readings)
+}
+
+pub fn render_decoded_readings(readings: &[DecodedReading]) -> String {
+    let mut table = String::new();
+    for (rank, reading) in readings.iter().enumerate() {
+        let kind = match reading.mapping {
+            Mapping::Shapes(_) => "shape",
+            Mapping::Outcomes(_) => "outcome",
+        };
+        writeln!(
+            table,
+            "{:>2}. {:<7} {:<36} {}",
+            rank + 1,
+            kind,
+            reading.mapping.to_string(),
+            reading.score
+        )
+        .unwrap();
+    }
+    table
+}
+
+// Bad lines are never skipped here: every mapping has to be scored on the
+// same rounds for the ranking to mean anything.
+fn parse_columns(
+    line_number: usize,
+    line: &str,
+    mode: ParseMode,
+) -> Result<Option<(Move, usize)>, RoundParseError> {
+    let Some([opp, mine]) = split_columns(line_number, line, mode)? else {
+        return Ok(None);
+    };
+    let error = |column, kind| RoundParseError {
+        line: line_number,
+        column,
+        text: line.to_string(),
+        kind,
+    };
+
+    let opponent_move = parse_move(&opp.text).map_err(|kind| error(opp.column, kind))?;
+    let code = CODES
+        .iter()
+        .position(|&code| code == mine.text)
+        .ok_or_else(|| {
+            error(
+                mine.column,
+                RoundParseErrorKind::InvalidShape(mine.text.clone()),
+            )
+        })?;
+    Ok(Some((opponent_move, code)))
+}
+
+fn apply_mapping(columns: &[(Move, usize)], mapping: &Mapping) -> Vec<Round> {
+    columns
+        .iter()
+        .map(|(opponent_move, code)| Round {
+            opponent_move: opponent_move.clone(),
+            my_move: match mapping {
+                Mapping::Shapes(moves) => moves[*code].clone(),
+                Mapping::Outcomes(outcomes) => move_for_outcome(outcomes[*code], opponent_move),
+            },
+        })
+        .collect()
+}
+
+fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+            permutations(&rest).into_iter().map(move |mut permutation| {
+                permutation.insert(0, first.clone());
+                permutation
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_strategy_guide, Mapping};
+    use crate::{game::Outcome, parsing::ParseMode, Move};
+
+    #[test]
+    fn every_mapping_is_scored() {
+        let readings = decode_strategy_guide("A Y\nB X\nC Z", ParseMode::Strict).unwrap();
+
+        assert_eq!(readings.len(), 12);
+    }
+
+    #[test]
+    fn puzzle_readings_score_as_expected() {
+        let readings = decode_strategy_guide("A Y\nB X\nC Z", ParseMode::Strict).unwrap();
+        let score_of = |mapping: Mapping| {
+            readings
+                .iter()
+                .find(|reading| reading.mapping == mapping)
+                .unwrap()
+                .score
+        };
+
+        assert_eq!(
+            score_of(Mapping::Shapes([Move::Rock, Move::Paper, Move::Scissors])),
+            15
+        );
+        assert_eq!(
+            score_of(Mapping::Outcomes([
+                Outcome::Loss,
+                Outcome::Draw,
+                Outcome::Win
+            ])),
+            12
+        );
+    }
+
+    #[test]
+    fn readings_are_ranked_from_best_to_worst() {
+        let readings = decode_strategy_guide("A Y\nB X\nC Z", ParseMode::Strict).unwrap();
+
+        assert!(readings.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+}
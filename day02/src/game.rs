@@ -0,0 +1,515 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::parsing::{
+    split_columns, ParseMode, ParsedGuide, RoundParseError, RoundParseErrorKind, Token,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    pub name: String,
+    pub value: i32,
+    pub opponent_code: String,
+    pub player_code: String,
+}
+
+impl Shape {
+    pub fn new(name: &str, value: i32, opponent_code: &str, player_code: &str) -> Shape {
+        Shape {
+            name: name.to_string(),
+            value,
+            opponent_code: opponent_code.to_string(),
+            player_code: player_code.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomePoints {
+    pub win: i32,
+    pub draw: i32,
+    pub loss: i32,
+}
+
+pub const STANDARD_POINTS: OutcomePoints = OutcomePoints {
+    win: 6,
+    draw: 3,
+    loss: 0,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRound {
+    pub opponent: usize,
+    pub me: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameDefinitionError {
+    TooFewShapes,
+    EvenShapeCount(usize),
+    DuplicateName(String),
+    DuplicateCode(String),
+    UnknownShape(String),
+    BeatsItself(String),
+    BeatsEachOther(String, String),
+    Undecided(String, String),
+    Syntax { line: usize, text: String },
+}
+
+impl Display for GameDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameDefinitionError::TooFewShapes => write!(f, "a game needs at least two shapes"),
+            GameDefinitionError::EvenShapeCount(count) => write!(
+                f,
+                "a cyclic game needs an odd number of shapes, got {}",
+                count
+            ),
+            GameDefinitionError::DuplicateName(name) => write!(f, "{} is defined twice", name),
+            GameDefinitionError::DuplicateCode(code) => {
+                write!(f, "code {} is used by more than one shape", code)
+            }
+            GameDefinitionError::UnknownShape(name) => write!(f, "{} is not a shape", name),
+            GameDefinitionError::BeatsItself(name) => write!(f, "{} can't beat itself", name),
+            GameDefinitionError::BeatsEachOther(a, b) => {
+                write!(f, "{} and {} can't beat each other", a, b)
+            }
+            GameDefinitionError::Undecided(a, b) => {
+                write!(f, "neither {} nor {} beats the other", a, b)
+            }
+            GameDefinitionError::Syntax { line, text } => {
+                write!(f, "line {}: can't make sense of {:?}", line, text)
+            }
+        }
+    }
+}
+
+impl Error for GameDefinitionError {}
+
+// A game where every pair of distinct shapes has exactly one winner, so that
+// each round ends in a win, a draw or a loss.
+#[derive(Debug, Clone)]
+pub struct Game {
+    shapes: Vec<Shape>,
+    beats: Vec<Vec<bool>>,
+    points: OutcomePoints,
+}
+
+impl Game {
+    pub fn new(
+        shapes: Vec<Shape>,
+        beats: &[(usize, usize)],
+        points: OutcomePoints,
+    ) -> Result<Game, GameDefinitionError> {
+        validate_shapes(&shapes)?;
+
+        let mut matrix = vec![vec![false; shapes.len()]; shapes.len()];
+        for &(winner, loser) in beats {
+            if winner == loser {
+                return Err(GameDefinitionError::BeatsItself(
+                    shapes[winner].name.clone(),
+                ));
+            }
+            matrix[winner][loser] = true;
+        }
+
+        for a in 0..shapes.len() {
+            for b in a + 1..shapes.len() {
+                let (name_a, name_b) = (shapes[a].name.clone(), shapes[b].name.clone());
+                match (matrix[a][b], matrix[b][a]) {
+                    (true, true) => {
+                        return Err(GameDefinitionError::BeatsEachOther(name_a, name_b))
+                    }
+                    (false, false) => return Err(GameDefinitionError::Undecided(name_a, name_b)),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Game {
+            shapes,
+            beats: matrix,
+            points,
+        })
+    }
+
+    // Each shape beats the (N - 1) / 2 shapes listed right before it, wrapping
+    // around, so every shape wins and loses against the same number of shapes.
+    pub fn cyclic(shapes: Vec<Shape>, points: OutcomePoints) -> Result<Game, GameDefinitionError> {
+        let count = shapes.len();
+        if count.is_multiple_of(2) {
+            return Err(GameDefinitionError::EvenShapeCount(count));
+        }
+
+        let beats: Vec<_> = (0..count)
+            .flat_map(|winner| (1..=count / 2).map(move |d| (winner, (winner + count - d) % count)))
+            .collect();
+        Game::new(shapes, &beats, points)
+    }
+
+    pub fn rock_paper_scissors() -> Game {
+        let shapes = vec![
+            Shape::new("Rock", 1, "A", "X"),
+            Shape::new("Paper", 2, "B", "Y"),
+            Shape::new("Scissors", 3, "C", "Z"),
+        ];
+        Game::cyclic(shapes, STANDARD_POINTS).unwrap()
+    }
+
+    // Listed in cyclic order rather than by value, see `cyclic`.
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        let shapes = vec![
+            Shape::new("Rock", 1, "A", "V"),
+            Shape::new("Spock", 5, "E", "Z"),
+            Shape::new("Paper", 2, "B", "W"),
+            Shape::new("Lizard", 4, "D", "Y"),
+            Shape::new("Scissors", 3, "C", "X"),
+        ];
+        Game::cyclic(shapes, STANDARD_POINTS).unwrap()
+    }
+
+    // Shapes are named S1, S2... with opponent codes A, B... and player codes
+    // a, b... Past the 26th shape codes continue like spreadsheet columns: AA,
+    // AB... and aa, ab...
+    pub fn numbered_cyclic(count: usize) -> Result<Game, GameDefinitionError> {
+        let shapes = (0..count)
+            .map(|i| {
+                Shape::new(
+                    &format!("S{}", i + 1),
+                    i as i32 + 1,
+                    &column_code(i, b'A'),
+                    &column_code(i, b'a'),
+                )
+            })
+            .collect();
+        Game::cyclic(shapes, STANDARD_POINTS)
+    }
+
+    pub fn outcome(&self, me: usize, opponent: usize) -> Outcome {
+        if self.beats[me][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][me] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn shape_points(&self, shape: usize) -> i32 {
+        self.shapes[shape].value
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> i32 {
+        match outcome {
+            Outcome::Win => self.points.win,
+            Outcome::Draw => self.points.draw,
+            Outcome::Loss => self.points.loss,
+        }
+    }
+
+    pub fn round_score(&self, &GameRound { opponent, me }: &GameRound) -> i32 {
+        self.shape_points(me) + self.outcome_points(self.outcome(me, opponent))
+    }
+
+    pub fn score(&self, rounds: &[GameRound]) -> i32 {
+        rounds.iter().map(|round| self.round_score(round)).sum()
+    }
+
+    // Same rules as the strategy guide parser: in lenient mode bad lines are
+    // skipped and reported, and codes may be written in any case.
+    pub fn parse_rounds(
+        &self,
+        s: &str,
+        mode: ParseMode,
+    ) -> Result<ParsedGuide<GameRound>, RoundParseError> {
+        let mut guide = ParsedGuide {
+            rounds: vec![],
+            errors: vec![],
+        };
+
+        for (i, line) in s.lines().enumerate() {
+            match self.parse_round(i + 1, line, mode) {
+                Ok(Some(round)) => guide.rounds.push(round),
+                Ok(None) => {}
+                Err(err) if mode == ParseMode::Lenient => guide.errors.push(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(guide)
+    }
+
+    fn parse_round(
+        &self,
+        line_number: usize,
+        line: &str,
+        mode: ParseMode,
+    ) -> Result<Option<GameRound>, RoundParseError> {
+        let Some([opp, mine]) = split_columns(line_number, line, mode)? else {
+            return Ok(None);
+        };
+        let find = |token: &Token, code: fn(&Shape) -> &str, kind: fn(String) -> _| {
+            self.shapes
+                .iter()
+                .position(|shape| code(shape) == token.text)
+                .or_else(|| {
+                    let lenient = mode == ParseMode::Lenient;
+                    self.shapes
+                        .iter()
+                        .position(|shape| lenient && code(shape).eq_ignore_ascii_case(&token.text))
+                })
+                .ok_or_else(|| RoundParseError {
+                    line: line_number,
+                    column: token.column,
+                    text: line.to_string(),
+                    kind: kind(token.text.clone()),
+                })
+        };
+
+        Ok(Some(GameRound {
+            opponent: find(
+                &opp,
+                |shape| &shape.opponent_code,
+                RoundParseErrorKind::InvalidOpponentMove,
+            )?,
+            me: find(
+                &mine,
+                |shape| &shape.player_code,
+                RoundParseErrorKind::InvalidShape,
+            )?,
+        }))
+    }
+}
+
+fn column_code(mut index: usize, first_letter: u8) -> String {
+    let mut code = vec![];
+    loop {
+        code.push((first_letter + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    code.iter().rev().collect()
+}
+
+fn validate_shapes(shapes: &[Shape]) -> Result<(), GameDefinitionError> {
+    if shapes.len() < 2 {
+        return Err(GameDefinitionError::TooFewShapes);
+    }
+
+    let mut names = HashSet::new();
+    let mut opponent_codes = HashSet::new();
+    let mut player_codes = HashSet::new();
+    for shape in shapes {
+        if !names.insert(&shape.name) {
+            return Err(GameDefinitionError::DuplicateName(shape.name.clone()));
+        }
+        if !opponent_codes.insert(&shape.opponent_code) {
+            return Err(GameDefinitionError::DuplicateCode(
+                shape.opponent_code.clone(),
+            ));
+        }
+        if !player_codes.insert(&shape.player_code) {
+            return Err(GameDefinitionError::DuplicateCode(
+                shape.player_code.clone(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// One statement per line, blank lines and lines starting with # are ignored:
+//
+//   shape <name> <value> <opponent code> <player code>
+//   beats <winner> <loser>
+//   points <win> <draw> <loss>
+//
+// Without any `beats` line, the shapes are assumed to be in cyclic order.
+pub fn parse_game_definition(s: &str) -> Result<Game, GameDefinitionError> {
+    let mut shapes = vec![];
+    let mut beats = vec![];
+    let mut points = STANDARD_POINTS;
+
+    for (i, line) in s.lines().enumerate() {
+        let syntax_error = || GameDefinitionError::Syntax {
+            line: i + 1,
+            text: line.to_string(),
+        };
+        let words: Vec<_> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] => {}
+            [comment, ..] if comment.starts_with('#') => {}
+            ["shape", name, value, opponent_code, player_code] => {
+                let value = value.parse().map_err(|_| syntax_error())?;
+                shapes.push(Shape::new(name, value, opponent_code, player_code));
+            }
+            ["beats", winner, loser] => beats.push((winner.to_string(), loser.to_string())),
+            ["points", win, draw, loss] => {
+                points = OutcomePoints {
+                    win: win.parse().map_err(|_| syntax_error())?,
+                    draw: draw.parse().map_err(|_| syntax_error())?,
+                    loss: loss.parse().map_err(|_| syntax_error())?,
+                }
+            }
+            _ => return Err(syntax_error()),
+        }
+    }
+
+    if beats.is_empty() {
+        return Game::cyclic(shapes, points);
+    }
+
+    let index_of = |name: &str| {
+        shapes
+            .iter()
+            .position(|shape| shape.name == name)
+            .ok_or_else(|| GameDefinitionError::UnknownShape(name.to_string()))
+    };
+    let beats = beats
+        .iter()
+        .map(|(winner, loser)| Ok((index_of(winner)?, index_of(loser)?)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Game::new(shapes, &beats, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        column_code, parse_game_definition, Game, GameDefinitionError, GameRound, Outcome,
+    };
+    use crate::parsing::{ParseMode, RoundParseErrorKind};
+
+    #[test]
+    fn rock_paper_scissors_preset_scores_the_example() {
+        let game = Game::rock_paper_scissors();
+
+        let rounds = game
+            .parse_rounds("A Y\nB X\nC Z", ParseMode::Strict)
+            .unwrap()
+            .rounds;
+
+        assert_eq!(game.score(&rounds), 15);
+    }
+
+    #[test]
+    fn every_rpsls_shape_beats_exactly_two_others() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let index = |name: &str| game.shapes.iter().position(|s| s.name == name).unwrap();
+
+        for me in 0..5 {
+            let wins = (0..5)
+                .filter(|&opponent| game.outcome(me, opponent) == Outcome::Win)
+                .count();
+            assert_eq!(wins, 2);
+        }
+        assert_eq!(
+            game.outcome(index("Spock"), index("Scissors")),
+            Outcome::Win
+        );
+        assert_eq!(game.outcome(index("Lizard"), index("Paper")), Outcome::Win);
+        assert_eq!(game.outcome(index("Rock"), index("Paper")), Outcome::Loss);
+    }
+
+    #[test]
+    fn cyclic_games_need_an_odd_number_of_shapes() {
+        assert!(Game::numbered_cyclic(7).is_ok());
+        assert_eq!(
+            Game::numbered_cyclic(4).unwrap_err(),
+            GameDefinitionError::EvenShapeCount(4)
+        );
+    }
+
+    #[test]
+    fn numbered_games_past_26_shapes_use_longer_codes() {
+        let game = Game::numbered_cyclic(29).unwrap();
+        let codes: Vec<_> = game.shapes[24..]
+            .iter()
+            .map(|shape| (shape.opponent_code.as_str(), shape.player_code.as_str()))
+            .collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                ("Y", "y"),
+                ("Z", "z"),
+                ("AA", "aa"),
+                ("AB", "ab"),
+                ("AC", "ac")
+            ]
+        );
+        assert_eq!(column_code(26 * 27, b'A'), "AAA");
+    }
+
+    #[test]
+    fn definition_with_custom_codes_beats_and_points() {
+        let definition = "\
+            # rock paper scissors, with different letters\n\
+            shape Rock 1 R r\n\
+            shape Paper 2 P p\n\
+            shape Scissors 3 S s\n\
+            beats Paper Rock\n\
+            beats Scissors Paper\n\
+            beats Rock Scissors\n\
+            points 10 5 -1\n";
+
+        let game = parse_game_definition(definition).unwrap();
+        let rounds = game
+            .parse_rounds("R p\nP r\nS s", ParseMode::Strict)
+            .unwrap()
+            .rounds;
+
+        assert_eq!(rounds[0], GameRound { opponent: 0, me: 1 });
+        assert_eq!(game.score(&rounds), 20);
+    }
+
+    #[test]
+    fn definition_where_a_pair_has_no_winner_is_rejected() {
+        let definition = "\
+            shape Rock 1 A X\n\
+            shape Paper 2 B Y\n\
+            shape Scissors 3 C Z\n\
+            beats Paper Rock\n\
+            beats Rock Scissors\n";
+
+        let result = parse_game_definition(definition);
+
+        assert_eq!(
+            result.unwrap_err(),
+            GameDefinitionError::Undecided("Paper".to_string(), "Scissors".to_string())
+        );
+    }
+
+    #[test]
+    fn bad_rounds_are_errors_with_their_position() {
+        let game = Game::numbered_cyclic(29).unwrap();
+
+        let err = game
+            .parse_rounds("A b\nAA zz", ParseMode::Strict)
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(
+            err.kind,
+            RoundParseErrorKind::InvalidShape("zz".to_string())
+        );
+
+        let guide = game
+            .parse_rounds("a B\nAB\n\nQQ a", ParseMode::Lenient)
+            .unwrap();
+        assert_eq!(guide.rounds, vec![GameRound { opponent: 0, me: 1 }]);
+        assert_eq!(guide.errors.len(), 2);
+    }
+}
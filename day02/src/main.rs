@@ -1,74 +1,318 @@
-use std::{env, fs};
+use std::{env, fs, process};
 
-#[derive(Debug, Clone)]
+use breakdown::{
+    break_down_score, explain_round, render_breakdown, render_breakdown_json, render_explanation,
+};
+use decoder::{decode_strategy_guide, render_decoded_readings};
+use game::{parse_game_definition, Game, GameRound, Outcome};
+use opponent_model::{
+    build_opponent_model, format_guide, generate_best_response_guide, render_model,
+};
+use parsing::{
+    parse_rounds, Notation, ParseMode, ParsedGuide, RoundParseError, StrategyInterpretation,
+};
+use strategy::{Copycat, FixedGuide, FrequencyCounter, Random, WinStayLoseShift};
+use tournament::{render_league_table, run_round_robin, StrategyFactory};
+
+mod breakdown;
+mod decoder;
+mod game;
+mod opponent_model;
+mod parsing;
+mod strategy;
+mod tournament;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Move {
     Rock,
     Paper,
     Scissors,
 }
 
+impl Move {
+    const ALL: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+    // Position of the shape in `Game::rock_paper_scissors`.
+    fn shape_index(&self) -> usize {
+        match self {
+            Move::Rock => 0,
+            Move::Paper => 1,
+            Move::Scissors => 2,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Round {
     opponent_move: Move,
     my_move: Move,
 }
 
-fn main() {
-    let file_content = read_file_from_args();
-    let rounds = parse_rounds(&file_content);
-    let score = calculate_score(&rounds);
+const USAGE: &str = "\
+usage: day02 <path> [--lenient] [--notation auto|letters|names|initials|emoji] [--interpretation shape|outcome|both] [--game rps|rpsls|cyclic-N|<definition path>]
+       day02 <path> --breakdown [--explain] [--json] [--interpretation shape|outcome]
+       day02 <path> --decode
+       day02 <path> --best-response [--interpretation shape|outcome]
+       day02 <path> --tournament [--rounds N] [--seed S] [--interpretation shape|outcome]";
+
+const DEFAULT_TOURNAMENT_ROUNDS: usize = 1000;
+const DEFAULT_SEED: u64 = 2022;
 
-    print!("{:?}", score);
+struct Options {
+    interpretations: &'static [StrategyInterpretation],
+    game: Option<String>,
+    decode: bool,
+    best_response: bool,
+    tournament: bool,
+    rounds: usize,
+    seed: u64,
+    mode: ParseMode,
+    notation: Notation,
+    breakdown: bool,
+    explain: bool,
+    json: bool,
 }
 
-fn read_file_from_args() -> String {
+fn main() {
     let args: Vec<String> = env::args().collect();
+    let file_content = read_file_from_args(&args);
+    let options = parse_options(&args[2..]);
+
+    if options.breakdown || options.explain {
+        let rounds = load_rounds(&file_content, options.interpretations[0], &options);
+        let game = Game::rock_paper_scissors();
+        let breakdown = break_down_score(&game, &rounds);
+        let explanations: Vec<_> = rounds
+            .iter()
+            .map(|round| explain_round(&game, round))
+            .collect();
+
+        if options.json {
+            let explanations = options.explain.then_some(explanations.as_slice());
+            print!("{}", render_breakdown_json(&breakdown, explanations));
+            return;
+        }
+        if options.explain {
+            for explanation in &explanations {
+                println!("{}", render_explanation(explanation));
+            }
+            println!();
+        }
+        print!("{}", render_breakdown(&breakdown));
+        return;
+    }
+
+    if options.decode {
+        let readings =
+            decode_strategy_guide(&file_content, options.mode).unwrap_or_else(exit_with_error);
+        print!("{}", render_decoded_readings(&readings));
+        return;
+    }
+
+    if options.best_response {
+        let guide_rounds = load_rounds(&file_content, options.interpretations[0], &options);
+        let opponent_moves: Vec<_> = guide_rounds
+            .iter()
+            .map(|round| round.opponent_move.clone())
+            .collect();
+        let model = build_opponent_model(&opponent_moves);
+        let generated = generate_best_response_guide(&opponent_moves, &model);
+
+        print!("{}", format_guide(&generated.rounds));
+        eprint!("{}", render_model(&model));
+        eprintln!("expected score:        {:.1}", generated.expected_score);
+        eprintln!(
+            "generated guide score: {}",
+            calculate_score(&generated.rounds)
+        );
+        eprintln!("original guide score:  {}", calculate_score(&guide_rounds));
+        return;
+    }
+
+    if options.tournament {
+        let guide_moves: Vec<_> = load_rounds(&file_content, options.interpretations[0], &options)
+            .into_iter()
+            .map(|round| round.my_move)
+            .collect();
+        let seed = options.seed;
+        let players: Vec<StrategyFactory> = vec![
+            Box::new(move || Box::new(FixedGuide::new(guide_moves.clone()))),
+            Box::new(move || Box::new(Random::new(seed))),
+            Box::new(|| Box::new(FrequencyCounter::new())),
+            Box::new(|| Box::new(WinStayLoseShift)),
+            Box::new(|| Box::new(Copycat)),
+        ];
+
+        let standings = run_round_robin(&players, options.rounds);
+        print!("{}", render_league_table(&standings));
+        return;
+    }
+
+    if let Some(game) = &options.game {
+        let game = load_game(game);
+        let rounds = take_rounds(game.parse_rounds(&file_content, options.mode));
+        print!("{:?}", game.score(&rounds));
+        return;
+    }
+
+    let interpretations = options.interpretations;
+    for &interpretation in interpretations {
+        let rounds = load_rounds(&file_content, interpretation, &options);
+        let score = calculate_score(&rounds);
+
+        if interpretations.len() == 1 {
+            print!("{:?}", score);
+        } else {
+            println!("{:?}: {:?}", interpretation, score);
+        }
+    }
+}
+
+fn read_file_from_args(args: &[String]) -> String {
     let path = args.get(1).unwrap();
     fs::read_to_string(path).unwrap()
 }
 
-fn parse_rounds(s: &str) -> Vec<Round> {
-    s.lines().map(parse_round).collect()
+fn parse_options(args: &[String]) -> Options {
+    let mut options = Options {
+        interpretations: &[StrategyInterpretation::Outcome],
+        game: None,
+        decode: false,
+        best_response: false,
+        tournament: false,
+        rounds: DEFAULT_TOURNAMENT_ROUNDS,
+        seed: DEFAULT_SEED,
+        mode: ParseMode::Strict,
+        notation: Notation::Auto,
+        breakdown: false,
+        explain: false,
+        json: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interpretation" => {
+                options.interpretations = parse_interpretations(option_value(&mut args))
+            }
+            "--game" => options.game = Some(option_value(&mut args).clone()),
+            "--lenient" => options.mode = ParseMode::Lenient,
+            "--notation" => options.notation = parse_notation(option_value(&mut args)),
+            "--breakdown" => options.breakdown = true,
+            "--explain" => options.explain = true,
+            "--json" => options.json = true,
+            "--decode" => options.decode = true,
+            "--best-response" => options.best_response = true,
+            "--tournament" => options.tournament = true,
+            "--rounds" => options.rounds = option_value(&mut args).parse().unwrap(),
+            "--seed" => options.seed = option_value(&mut args).parse().unwrap(),
+            _ => panic!("{}", USAGE),
+        }
+    }
+
+    let single_interpretation =
+        options.breakdown || options.explain || options.best_response || options.tournament;
+    if options.json && !(options.breakdown || options.explain) {
+        panic!("--json needs --breakdown or --explain\n{}", USAGE);
+    }
+    if single_interpretation && options.interpretations.len() > 1 {
+        panic!(
+            "--interpretation both only applies to the total score\n{}",
+            USAGE
+        );
+    }
+    options
+}
+
+fn load_rounds(
+    file_content: &str,
+    interpretation: StrategyInterpretation,
+    options: &Options,
+) -> Vec<Round> {
+    take_rounds(parse_rounds(
+        file_content,
+        interpretation,
+        options.mode,
+        options.notation,
+    ))
+}
+
+// Skipped lines are reported on stderr once the whole guide has been read.
+fn take_rounds<R>(guide: Result<ParsedGuide<R>, RoundParseError>) -> Vec<R> {
+    let ParsedGuide { rounds, errors } = guide.unwrap_or_else(exit_with_error);
+
+    if !errors.is_empty() {
+        eprintln!("skipped {} invalid line(s):", errors.len());
+        for err in errors {
+            eprintln!("  {}", err);
+        }
+    }
+    rounds
 }
 
-fn parse_round(round: &str) -> Round {
-    let (opp, result) = round.split_once(" ").unwrap();
-    let opponent_move = parse_move(opp);
-    let my_move = parse_my_move(result, &opponent_move);
+fn exit_with_error<T>(err: impl std::error::Error) -> T {
+    eprintln!("error: {}", err);
+    process::exit(1)
+}
 
-    Round {
-        opponent_move,
-        my_move,
+fn option_value<'a>(args: &mut impl Iterator<Item = &'a String>) -> &'a String {
+    args.next().unwrap_or_else(|| panic!("{}", USAGE))
+}
+
+fn parse_notation(value: &str) -> Notation {
+    match value {
+        "auto" => Notation::Auto,
+        "letters" => Notation::Letters,
+        "names" => Notation::Names,
+        "initials" => Notation::Initials,
+        "emoji" => Notation::Emoji,
+        _ => panic!("{} is not a valid notation", value),
     }
 }
 
-fn parse_move(s: &str) -> Move {
-    match s {
-        "A" => Move::Rock,
-        "B" => Move::Paper,
-        "C" => Move::Scissors,
-        &_ => panic!("{} is not a valid move", s),
+fn parse_interpretations(value: &str) -> &'static [StrategyInterpretation] {
+    match value {
+        "shape" => &[StrategyInterpretation::Shape],
+        "outcome" => &[StrategyInterpretation::Outcome],
+        "both" => &[
+            StrategyInterpretation::Shape,
+            StrategyInterpretation::Outcome,
+        ],
+        _ => panic!("{} is not a valid interpretation", value),
     }
 }
 
-fn parse_my_move(result: &str, opponent_move: &Move) -> Move {
-    match result {
-        "X" => match opponent_move {
+fn load_game(name: &str) -> Game {
+    let game = match name {
+        "rps" => Ok(Game::rock_paper_scissors()),
+        "rpsls" => Ok(Game::rock_paper_scissors_lizard_spock()),
+        _ => match name.strip_prefix("cyclic-") {
+            Some(count) => Game::numbered_cyclic(count.parse().unwrap()),
+            None => parse_game_definition(&fs::read_to_string(name).unwrap()),
+        },
+    };
+
+    game.unwrap_or_else(|err| panic!("invalid game {}: {}", name, err))
+}
+
+fn move_for_outcome(outcome: Outcome, opponent_move: &Move) -> Move {
+    match outcome {
+        Outcome::Loss => match opponent_move {
             Move::Rock => Move::Scissors,
             Move::Paper => Move::Rock,
             Move::Scissors => Move::Paper,
         },
-        "Y" => opponent_move.clone(),
-        "Z" => match opponent_move {
+        Outcome::Draw => opponent_move.clone(),
+        Outcome::Win => match opponent_move {
             Move::Rock => Move::Paper,
             Move::Paper => Move::Scissors,
             Move::Scissors => Move::Rock,
         },
-        &_ => panic!("{} is not a valid result", result),
     }
 }
 
-fn calculate_score(rounds: &Vec<Round>) -> i32 {
+fn calculate_score(rounds: &[Round]) -> i32 {
+    let game = Game::rock_paper_scissors();
     rounds
         .iter()
         .map(
@@ -76,30 +320,49 @@ fn calculate_score(rounds: &Vec<Round>) -> i32 {
                  opponent_move,
                  my_move,
              }| {
-                match my_move {
-                    Move::Rock => {
-                        1 + (match opponent_move {
-                            Move::Rock => 3,
-                            Move::Paper => 0,
-                            Move::Scissors => 6,
-                        })
-                    }
-                    Move::Paper => {
-                        2 + (match opponent_move {
-                            Move::Rock => 6,
-                            Move::Paper => 3,
-                            Move::Scissors => 0,
-                        })
-                    }
-                    Move::Scissors => {
-                        3 + (match opponent_move {
-                            Move::Rock => 0,
-                            Move::Paper => 6,
-                            Move::Scissors => 3,
-                        })
-                    }
-                }
+                game.round_score(&GameRound {
+                    opponent: opponent_move.shape_index(),
+                    me: my_move.shape_index(),
+                })
             },
         )
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        calculate_score,
+        parsing::{parse_rounds, Notation, ParseMode, StrategyInterpretation},
+    };
+
+    const EXAMPLE_INPUT: &str = "A Y\nB X\nC Z";
+
+    #[test]
+    fn example_read_as_shapes() {
+        let rounds = parse_rounds(
+            EXAMPLE_INPUT,
+            StrategyInterpretation::Shape,
+            ParseMode::Strict,
+            Notation::Letters,
+        )
+        .unwrap()
+        .rounds;
+
+        assert_eq!(calculate_score(&rounds), 15);
+    }
+
+    #[test]
+    fn example_read_as_outcomes() {
+        let rounds = parse_rounds(
+            EXAMPLE_INPUT,
+            StrategyInterpretation::Outcome,
+            ParseMode::Strict,
+            Notation::Letters,
+        )
+        .unwrap()
+        .rounds;
+
+        assert_eq!(calculate_score(&rounds), 12);
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt::Write;
+
+use crate::{calculate_score, Move, Round};
+
+type Distribution = [f64; 3];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpponentModel {
+    pub frequencies: Distribution,
+    // Indexed by the previous move, None when that move was never followed by another.
+    pub transitions: [Option<Distribution>; 3],
+}
+
+#[derive(Debug)]
+pub struct GeneratedGuide {
+    pub rounds: Vec<Round>,
+    pub expected_score: f64,
+}
+
+pub fn build_opponent_model(opponent_moves: &[Move]) -> OpponentModel {
+    let mut counts = [0usize; 3];
+    let mut transition_counts = [[0usize; 3]; 3];
+
+    for opponent_move in opponent_moves {
+        counts[opponent_move.shape_index()] += 1;
+    }
+    for pair in opponent_moves.windows(2) {
+        transition_counts[pair[0].shape_index()][pair[1].shape_index()] += 1;
+    }
+
+    OpponentModel {
+        frequencies: normalize(&counts).unwrap_or([1.0 / 3.0; 3]),
+        transitions: transition_counts.map(|row| normalize(&row)),
+    }
+}
+
+impl OpponentModel {
+    // Falls back on the overall frequencies when nothing is known about what
+    // follows the previous move.
+    pub fn prediction(&self, previous_move: Option<&Move>) -> Distribution {
+        previous_move
+            .and_then(|previous_move| self.transitions[previous_move.shape_index()])
+            .unwrap_or(self.frequencies)
+    }
+}
+
+// Each round is answered with the move that maximizes the expected score,
+// given what the opponent played in the round before. There are only four
+// possible predictions, so their best responses are worked out up front.
+pub fn generate_best_response_guide(
+    opponent_moves: &[Move],
+    model: &OpponentModel,
+) -> GeneratedGuide {
+    let after_move =
+        Move::ALL.map(|previous_move| best_response(&model.prediction(Some(&previous_move))));
+    let first_round = best_response(&model.prediction(None));
+    let mut rounds = Vec::with_capacity(opponent_moves.len());
+    let mut expected_score = 0.0;
+
+    for (i, opponent_move) in opponent_moves.iter().enumerate() {
+        let (my_move, expected) = match i.checked_sub(1) {
+            Some(previous) => after_move[opponent_moves[previous].shape_index()].clone(),
+            None => first_round.clone(),
+        };
+
+        rounds.push(Round {
+            opponent_move: opponent_move.clone(),
+            my_move,
+        });
+        expected_score += expected;
+    }
+
+    GeneratedGuide {
+        rounds,
+        expected_score,
+    }
+}
+
+pub fn best_response(prediction: &Distribution) -> (Move, f64) {
+    let mut best: Option<(Move, f64)> = None;
+
+    for my_move in Move::ALL {
+        let expected: f64 = Move::ALL
+            .iter()
+            .zip(prediction)
+            .map(|(opponent_move, probability)| {
+                let round = Round {
+                    opponent_move: opponent_move.clone(),
+                    my_move: my_move.clone(),
+                };
+                probability * calculate_score(&[round]) as f64
+            })
+            .sum();
+
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_expected)| expected > *best_expected)
+        {
+            best = Some((my_move, expected));
+        }
+    }
+
+    best.unwrap()
+}
+
+// Written with X/Y/Z as shapes, since outcomes can't express a chosen move
+// independently of the opponent.
+pub fn format_guide(rounds: &[Round]) -> String {
+    let mut guide = String::new();
+    for Round {
+        opponent_move,
+        my_move,
+    } in rounds
+    {
+        let opponent_code = ["A", "B", "C"][opponent_move.shape_index()];
+        let my_code = ["X", "Y", "Z"][my_move.shape_index()];
+        writeln!(guide, "{} {}", opponent_code, my_code).unwrap();
+    }
+    guide
+}
+
+pub fn render_model(model: &OpponentModel) -> String {
+    let mut report = String::from("frequencies:\n");
+    for (opponent_move, probability) in Move::ALL.iter().zip(model.frequencies) {
+        writeln!(
+            report,
+            "  {:<8} {:.3}",
+            format!("{:?}", opponent_move),
+            probability
+        )
+        .unwrap();
+    }
+
+    report.push_str("transitions (previous -> Rock Paper Scissors):\n");
+    for (previous_move, row) in Move::ALL.iter().zip(model.transitions) {
+        let cells = match row {
+            Some(row) => row.map(|p| format!("{:.3}", p)).join(" "),
+            None => "no data".to_string(),
+        };
+        writeln!(report, "  {:<8} {}", format!("{:?}", previous_move), cells).unwrap();
+    }
+    report
+}
+
+fn normalize(counts: &[usize; 3]) -> Option<Distribution> {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    Some(counts.map(|count| count as f64 / total as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_response, build_opponent_model, format_guide, generate_best_response_guide};
+    use crate::{calculate_score, Move};
+
+    #[test]
+    fn model_counts_frequencies_and_transitions() {
+        let moves = [Move::Rock, Move::Rock, Move::Paper, Move::Rock];
+
+        let model = build_opponent_model(&moves);
+
+        assert_eq!(model.frequencies, [0.75, 0.25, 0.0]);
+        assert_eq!(model.transitions[0], Some([0.5, 0.5, 0.0]));
+        assert_eq!(model.transitions[1], Some([1.0, 0.0, 0.0]));
+        assert_eq!(model.transitions[2], None);
+    }
+
+    #[test]
+    fn best_response_to_a_rock_player_is_paper() {
+        let (my_move, expected) = best_response(&[1.0, 0.0, 0.0]);
+
+        assert_eq!(my_move, Move::Paper);
+        assert_eq!(expected, 8.0);
+    }
+
+    #[test]
+    fn predictable_opponent_is_beaten_every_round_but_the_first() {
+        let moves = [
+            Move::Rock,
+            Move::Paper,
+            Move::Scissors,
+            Move::Rock,
+            Move::Paper,
+        ];
+        let model = build_opponent_model(&moves);
+
+        let guide = generate_best_response_guide(&moves, &model);
+
+        let wins = guide.rounds[1..]
+            .iter()
+            .all(|round| calculate_score(std::slice::from_ref(round)) >= 7);
+        assert!(wins);
+    }
+
+    #[test]
+    fn generated_guide_uses_xyz_as_shapes() {
+        let moves = [Move::Rock];
+        let guide = generate_best_response_guide(&moves, &build_opponent_model(&moves));
+
+        assert_eq!(format_guide(&guide.rounds), "A Y\n");
+    }
+}
@@ -0,0 +1,451 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{game::Outcome, move_for_outcome, Move, Round};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyInterpretation {
+    // X/Y/Z is the shape I play (part 1).
+    Shape,
+    // X/Y/Z is the outcome I'm asked to reach (part 2).
+    Outcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    // Exactly "<A|B|C> <X|Y|Z>" on every line, the first bad line aborts.
+    Strict,
+    // Blank lines, extra whitespace and lowercase letters are accepted, bad
+    // lines are skipped and reported along with the rounds.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    // Picks the first notation that makes sense of the first round.
+    Auto,
+    // A/B/C for the opponent, X/Y/Z for me, read with a `StrategyInterpretation`.
+    Letters,
+    // Rock/Paper/Scissors, in any case.
+    Names,
+    // R/P/S.
+    Initials,
+    // 🪨/📄/✂️ or ✊/✋/✌️.
+    Emoji,
+}
+
+const DETECTABLE_NOTATIONS: [Notation; 4] = [
+    Notation::Letters,
+    Notation::Names,
+    Notation::Initials,
+    Notation::Emoji,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundParseErrorKind {
+    EmptyLine,
+    MissingColumn,
+    UnexpectedInput(String),
+    InvalidOpponentMove(String),
+    InvalidShape(String),
+    InvalidOutcome(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: RoundParseErrorKind,
+}
+
+impl Display for RoundParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            RoundParseErrorKind::EmptyLine => write!(f, "empty line"),
+            RoundParseErrorKind::MissingColumn => {
+                write!(f, "expected two columns in {:?}", self.text)
+            }
+            RoundParseErrorKind::UnexpectedInput(s) => {
+                write!(f, "unexpected {:?} after the round", s)
+            }
+            RoundParseErrorKind::InvalidOpponentMove(s) => write!(f, "{} is not a valid move", s),
+            RoundParseErrorKind::InvalidShape(s) => write!(f, "{} is not a valid shape", s),
+            RoundParseErrorKind::InvalidOutcome(s) => write!(f, "{} is not a valid result", s),
+        }
+    }
+}
+
+impl Error for RoundParseError {}
+
+#[derive(Debug)]
+pub struct ParsedGuide<R = Round> {
+    pub rounds: Vec<R>,
+    pub errors: Vec<RoundParseError>,
+}
+
+pub struct Token {
+    pub text: String,
+    pub column: usize,
+}
+
+pub fn parse_rounds(
+    s: &str,
+    interpretation: StrategyInterpretation,
+    mode: ParseMode,
+    notation: Notation,
+) -> Result<ParsedGuide, RoundParseError> {
+    let notation = match notation {
+        Notation::Auto => detect_notation(s, mode),
+        notation => notation,
+    };
+    let mut guide = ParsedGuide {
+        rounds: vec![],
+        errors: vec![],
+    };
+
+    for (i, line) in s.lines().enumerate() {
+        match parse_round(i + 1, line, interpretation, mode, notation) {
+            Ok(Some(round)) => guide.rounds.push(round),
+            Ok(None) => {}
+            Err(err) if mode == ParseMode::Lenient => guide.errors.push(err),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(guide)
+}
+
+// Falls back on letters when the first round makes sense in no notation, so
+// that errors are reported against the puzzle's own format.
+pub fn detect_notation(s: &str, mode: ParseMode) -> Notation {
+    let first_round = s
+        .lines()
+        .enumerate()
+        .find(|(_, line)| !line.trim().is_empty());
+    let Some((i, line)) = first_round else {
+        return Notation::Letters;
+    };
+
+    DETECTABLE_NOTATIONS
+        .into_iter()
+        .find(|&notation| {
+            parse_round(i + 1, line, StrategyInterpretation::Shape, mode, notation).is_ok()
+        })
+        .unwrap_or(Notation::Letters)
+}
+
+fn parse_round(
+    line_number: usize,
+    line: &str,
+    interpretation: StrategyInterpretation,
+    mode: ParseMode,
+    notation: Notation,
+) -> Result<Option<Round>, RoundParseError> {
+    let Some([opp, mine]) = split_columns(line_number, line, mode)? else {
+        return Ok(None);
+    };
+    let error = |token: &Token, kind| RoundParseError {
+        line: line_number,
+        column: token.column,
+        text: line.to_string(),
+        kind,
+    };
+
+    let opponent_move = match notation {
+        Notation::Auto | Notation::Letters => parse_move(&opp.text),
+        notation => parse_shape(&opp.text, notation)
+            .ok_or_else(|| RoundParseErrorKind::InvalidOpponentMove(opp.text.clone())),
+    }
+    .map_err(|kind| error(&opp, kind))?;
+    let my_move = match (notation, interpretation) {
+        (Notation::Auto | Notation::Letters, StrategyInterpretation::Shape) => {
+            parse_my_shape(&mine.text)
+        }
+        (Notation::Auto | Notation::Letters, StrategyInterpretation::Outcome) => {
+            parse_my_move(&mine.text, &opponent_move)
+        }
+        (notation, _) => match parse_outcome_word(&mine.text) {
+            Some(outcome) => Ok(move_for_outcome(outcome, &opponent_move)),
+            None => parse_shape(&mine.text, notation)
+                .ok_or_else(|| RoundParseErrorKind::InvalidShape(mine.text.clone())),
+        },
+    }
+    .map_err(|kind| error(&mine, kind))?;
+
+    Ok(Some(Round {
+        opponent_move,
+        my_move,
+    }))
+}
+
+// Returns None for the blank lines that lenient mode skips. Columns are
+// counted in characters, starting at 1.
+pub fn split_columns(
+    line_number: usize,
+    line: &str,
+    mode: ParseMode,
+) -> Result<Option<[Token; 2]>, RoundParseError> {
+    let error = |column, kind| RoundParseError {
+        line: line_number,
+        column,
+        text: line.to_string(),
+        kind,
+    };
+
+    let mut tokens: Vec<Token> = match mode {
+        ParseMode::Strict => {
+            let mut column = 1;
+            line.split(' ')
+                .map(|text| {
+                    let token = Token {
+                        text: text.to_string(),
+                        column,
+                    };
+                    column += text.chars().count() + 1;
+                    token
+                })
+                .collect()
+        }
+        ParseMode::Lenient => whitespace_tokens(line)
+            .map(|(column, text)| Token {
+                text: text.to_uppercase(),
+                column,
+            })
+            .collect(),
+    };
+
+    match tokens.len() {
+        0 => Ok(None),
+        1 if tokens[0].text.is_empty() => Err(error(1, RoundParseErrorKind::EmptyLine)),
+        1 => Err(error(
+            line.chars().count() + 1,
+            RoundParseErrorKind::MissingColumn,
+        )),
+        2 => {
+            let mine = tokens.pop().unwrap();
+            let opp = tokens.pop().unwrap();
+            Ok(Some([opp, mine]))
+        }
+        _ => {
+            let extra = &tokens[2];
+            let rest: String = line.chars().skip(extra.column - 1).collect();
+            Err(error(
+                extra.column,
+                RoundParseErrorKind::UnexpectedInput(rest),
+            ))
+        }
+    }
+}
+
+fn whitespace_tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    let mut start = None;
+    let mut tokens = vec![];
+
+    for (byte_index, c) in line.char_indices() {
+        column += 1;
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, byte_index)),
+            (true, Some((token_column, token_start))) => {
+                tokens.push((token_column, &line[token_start..byte_index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((token_column, token_start)) = start {
+        tokens.push((token_column, &line[token_start..]));
+    }
+
+    tokens.into_iter()
+}
+
+pub fn parse_move(s: &str) -> Result<Move, RoundParseErrorKind> {
+    match s {
+        "A" => Ok(Move::Rock),
+        "B" => Ok(Move::Paper),
+        "C" => Ok(Move::Scissors),
+        &_ => Err(RoundParseErrorKind::InvalidOpponentMove(s.to_string())),
+    }
+}
+
+fn parse_shape(s: &str, notation: Notation) -> Option<Move> {
+    let shape = match notation {
+        Notation::Names => s.to_lowercase(),
+        // The variation selector only changes how the emoji is drawn.
+        Notation::Emoji => s.replace('\u{FE0F}', ""),
+        _ => s.to_string(),
+    };
+
+    match (notation, shape.as_str()) {
+        (Notation::Names, "rock") | (Notation::Initials, "R") => Some(Move::Rock),
+        (Notation::Names, "paper") | (Notation::Initials, "P") => Some(Move::Paper),
+        (Notation::Names, "scissors") | (Notation::Initials, "S") => Some(Move::Scissors),
+        (Notation::Emoji, "🪨" | "✊") => Some(Move::Rock),
+        (Notation::Emoji, "📄" | "✋") => Some(Move::Paper),
+        (Notation::Emoji, "✂" | "✌") => Some(Move::Scissors),
+        _ => None,
+    }
+}
+
+fn parse_outcome_word(s: &str) -> Option<Outcome> {
+    match s.to_lowercase().as_str() {
+        "lose" | "loss" => Some(Outcome::Loss),
+        "draw" => Some(Outcome::Draw),
+        "win" => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+fn parse_my_shape(s: &str) -> Result<Move, RoundParseErrorKind> {
+    match s {
+        "X" => Ok(Move::Rock),
+        "Y" => Ok(Move::Paper),
+        "Z" => Ok(Move::Scissors),
+        &_ => Err(RoundParseErrorKind::InvalidShape(s.to_string())),
+    }
+}
+
+fn parse_my_move(result: &str, opponent_move: &Move) -> Result<Move, RoundParseErrorKind> {
+    let outcome = match result {
+        "X" => Outcome::Loss,
+        "Y" => Outcome::Draw,
+        "Z" => Outcome::Win,
+        &_ => return Err(RoundParseErrorKind::InvalidOutcome(result.to_string())),
+    };
+    Ok(move_for_outcome(outcome, opponent_move))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        detect_notation, parse_rounds, Notation, ParseMode, RoundParseError, RoundParseErrorKind,
+        StrategyInterpretation,
+    };
+    use crate::Move;
+
+    fn parse_strictly(s: &str) -> RoundParseError {
+        parse_rounds(
+            s,
+            StrategyInterpretation::Shape,
+            ParseMode::Strict,
+            Notation::Letters,
+        )
+        .unwrap_err()
+    }
+
+    fn my_moves(s: &str, notation: Notation) -> Vec<Move> {
+        parse_rounds(
+            s,
+            StrategyInterpretation::Shape,
+            ParseMode::Strict,
+            notation,
+        )
+        .unwrap()
+        .rounds
+        .into_iter()
+        .map(|round| round.my_move)
+        .collect()
+    }
+
+    #[test]
+    fn strict_mode_reports_the_first_bad_line_with_its_column() {
+        let err = parse_strictly("A Y\nB Q\nD Z");
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "B Q");
+        assert_eq!(err.kind, RoundParseErrorKind::InvalidShape("Q".to_string()));
+    }
+
+    #[test]
+    fn strict_mode_rejects_missing_columns_and_extra_input() {
+        let missing = parse_strictly("AY");
+        let extra = parse_strictly("A Y Z");
+        let spaces = parse_strictly("A  Y");
+
+        assert_eq!(
+            (missing.column, missing.kind),
+            (3, RoundParseErrorKind::MissingColumn)
+        );
+        assert_eq!(
+            (extra.column, extra.kind),
+            (5, RoundParseErrorKind::UnexpectedInput("Z".to_string()))
+        );
+        assert_eq!(
+            (spaces.column, spaces.kind),
+            (4, RoundParseErrorKind::UnexpectedInput("Y".to_string()))
+        );
+    }
+
+    #[test]
+    fn lenient_mode_skips_bad_lines_and_reports_all_of_them() {
+        let input = "A Y\r\n\n  b   x \nC\nQ Z\nc z\r\n";
+
+        let guide = parse_rounds(
+            input,
+            StrategyInterpretation::Shape,
+            ParseMode::Lenient,
+            Notation::Letters,
+        )
+        .unwrap();
+
+        let moves: Vec<_> = guide.rounds.iter().map(|r| r.my_move.clone()).collect();
+        assert_eq!(moves, vec![Move::Paper, Move::Rock, Move::Scissors]);
+        let errors: Vec<_> = guide.errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(errors, vec![(4, 2), (5, 1)]);
+        assert_eq!(
+            guide.errors[1].kind,
+            RoundParseErrorKind::InvalidOpponentMove("Q".to_string())
+        );
+    }
+
+    #[test]
+    fn every_notation_maps_to_the_same_rounds() {
+        let expected = vec![Move::Paper, Move::Rock, Move::Scissors];
+
+        assert_eq!(my_moves("A Y\nB X\nC Z", Notation::Auto), expected);
+        assert_eq!(
+            my_moves("Rock Paper\npaper rock\nSCISSORS Scissors", Notation::Auto),
+            expected
+        );
+        assert_eq!(my_moves("R P\nP R\nS S", Notation::Auto), expected);
+        assert_eq!(my_moves("🪨 📄\n✋ ✊\n✂️ ✂", Notation::Auto), expected);
+    }
+
+    #[test]
+    fn outcome_words_are_read_against_the_opponent_move() {
+        let moves = my_moves("R win\nP lose\nS draw", Notation::Initials);
+
+        assert_eq!(moves, vec![Move::Paper, Move::Rock, Move::Scissors]);
+    }
+
+    #[test]
+    fn notation_is_detected_from_the_first_round() {
+        assert_eq!(
+            detect_notation("\nR win\n", ParseMode::Lenient),
+            Notation::Initials
+        );
+        assert_eq!(
+            detect_notation("Rock Paper", ParseMode::Strict),
+            Notation::Names
+        );
+        assert_eq!(detect_notation("? ?", ParseMode::Strict), Notation::Letters);
+    }
+
+    #[test]
+    fn explicit_notation_rejects_the_others() {
+        let err = parse_rounds(
+            "R P\nRock Paper",
+            StrategyInterpretation::Shape,
+            ParseMode::Strict,
+            Notation::Initials,
+        )
+        .unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{game::Outcome, move_for_outcome, Move, Round};
+
+// Chooses the next move from the rounds played so far, seen from the side of
+// the player: `my_move` is its own move and `opponent_move` the other's.
+pub trait Strategy {
+    fn name(&self) -> String;
+    fn choose(&mut self, history: &[Round]) -> Move;
+}
+
+// Replays the moves of a strategy guide, starting over when it runs out.
+pub struct FixedGuide {
+    moves: Vec<Move>,
+}
+
+impl FixedGuide {
+    pub fn new(moves: Vec<Move>) -> FixedGuide {
+        FixedGuide { moves }
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        "fixed guide".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        if self.moves.is_empty() {
+            return Move::Rock;
+        }
+        self.moves[history.len() % self.moves.len()].clone()
+    }
+}
+
+// xorshift64*, good enough to pick moves and reproducible from its seed.
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random {
+            seed,
+            state: seed.max(1),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random ({})", self.seed)
+    }
+
+    fn choose(&mut self, _history: &[Round]) -> Move {
+        Move::ALL[(self.next() % 3) as usize].clone()
+    }
+}
+
+// Beats whatever the opponent played the most so far.
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+    counted_rounds: usize,
+}
+
+impl FrequencyCounter {
+    pub fn new() -> FrequencyCounter {
+        FrequencyCounter {
+            counts: [0; 3],
+            counted_rounds: 0,
+        }
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    // Only the rounds played since the last call are counted; a history
+    // shorter than what was counted means a new match has started.
+    fn choose(&mut self, history: &[Round]) -> Move {
+        if history.len() < self.counted_rounds {
+            *self = FrequencyCounter::new();
+        }
+        for round in &history[self.counted_rounds..] {
+            self.counts[round.opponent_move.shape_index()] += 1;
+        }
+        self.counted_rounds = history.len();
+
+        let most_frequent = (0..3).rev().max_by_key(|&i| self.counts[i]).unwrap();
+        move_for_outcome(Outcome::Win, &Move::ALL[most_frequent])
+    }
+}
+
+// Keeps a winning move, otherwise moves on to the next shape.
+pub struct WinStayLoseShift;
+
+impl Strategy for WinStayLoseShift {
+    fn name(&self) -> String {
+        "win-stay/lose-shift".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        let Some(last) = history.last() else {
+            return Move::Rock;
+        };
+
+        if last.my_move == move_for_outcome(Outcome::Win, &last.opponent_move) {
+            return last.my_move.clone();
+        }
+        Move::ALL[(last.my_move.shape_index() + 1) % 3].clone()
+    }
+}
+
+// Plays the opponent's previous move.
+pub struct Copycat;
+
+impl Strategy for Copycat {
+    fn name(&self) -> String {
+        "copycat".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        history
+            .last()
+            .map(|round| round.opponent_move.clone())
+            .unwrap_or(Move::Rock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Copycat, FixedGuide, FrequencyCounter, Random, Strategy, WinStayLoseShift};
+    use crate::{Move, Round};
+
+    fn round(my_move: Move, opponent_move: Move) -> Round {
+        Round {
+            opponent_move,
+            my_move,
+        }
+    }
+
+    #[test]
+    fn fixed_guide_starts_over_when_it_runs_out() {
+        let mut player = FixedGuide::new(vec![Move::Paper, Move::Scissors]);
+        let history = [
+            round(Move::Paper, Move::Rock),
+            round(Move::Scissors, Move::Rock),
+        ];
+
+        assert_eq!(player.choose(&history[..1]), Move::Scissors);
+        assert_eq!(player.choose(&history), Move::Paper);
+    }
+
+    #[test]
+    fn random_players_with_the_same_seed_play_the_same_moves() {
+        let mut player_1 = Random::new(42);
+        let mut player_2 = Random::new(42);
+
+        let moves_1: Vec<_> = (0..20).map(|_| player_1.choose(&[])).collect();
+        let moves_2: Vec<_> = (0..20).map(|_| player_2.choose(&[])).collect();
+
+        assert_eq!(moves_1, moves_2);
+        assert!(moves_1.contains(&Move::Rock));
+        assert!(moves_1.contains(&Move::Paper));
+        assert!(moves_1.contains(&Move::Scissors));
+    }
+
+    #[test]
+    fn frequency_counter_beats_the_most_played_move() {
+        let history = [
+            round(Move::Rock, Move::Scissors),
+            round(Move::Rock, Move::Scissors),
+            round(Move::Rock, Move::Paper),
+        ];
+
+        let mut player = FrequencyCounter::new();
+
+        assert_eq!(player.choose(&history[..1]), Move::Rock);
+        assert_eq!(player.choose(&history[..2]), Move::Rock);
+        assert_eq!(player.choose(&history), Move::Rock);
+        assert_eq!(
+            player.choose(&[round(Move::Rock, Move::Paper)]),
+            Move::Scissors
+        );
+    }
+
+    #[test]
+    fn win_stay_lose_shift_keeps_winning_moves_only() {
+        let won = [round(Move::Paper, Move::Rock)];
+        let lost = [round(Move::Paper, Move::Scissors)];
+
+        assert_eq!(WinStayLoseShift.choose(&won), Move::Paper);
+        assert_eq!(WinStayLoseShift.choose(&lost), Move::Scissors);
+    }
+
+    #[test]
+    fn copycat_plays_the_previous_opponent_move() {
+        let history = [round(Move::Rock, Move::Scissors)];
+
+        assert_eq!(Copycat.choose(&[]), Move::Rock);
+        assert_eq!(Copycat.choose(&history), Move::Scissors);
+    }
+}
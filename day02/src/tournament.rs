@@ -0,0 +1,164 @@
+use std::fmt::Write;
+
+use crate::{calculate_score, strategy::Strategy, Round};
+
+pub type StrategyFactory = Box<dyn Fn() -> Box<dyn Strategy>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: i32,
+}
+
+// Plays one match of `round_count` rounds and returns both scores.
+pub fn play_match(
+    player_1: &mut dyn Strategy,
+    player_2: &mut dyn Strategy,
+    round_count: usize,
+) -> (i32, i32) {
+    let mut history_1 = Vec::with_capacity(round_count);
+    let mut history_2 = Vec::with_capacity(round_count);
+
+    for _ in 0..round_count {
+        let move_1 = player_1.choose(&history_1);
+        let move_2 = player_2.choose(&history_2);
+
+        history_1.push(Round {
+            opponent_move: move_2.clone(),
+            my_move: move_1.clone(),
+        });
+        history_2.push(Round {
+            opponent_move: move_1,
+            my_move: move_2,
+        });
+    }
+
+    (calculate_score(&history_1), calculate_score(&history_2))
+}
+
+// Every player meets every other player once, each match with fresh players.
+// The table is sorted by match wins, then draws, then total score.
+pub fn run_round_robin(players: &[StrategyFactory], round_count: usize) -> Vec<Standing> {
+    let mut standings: Vec<_> = players
+        .iter()
+        .map(|factory| Standing {
+            name: factory().name(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            score: 0,
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (score_i, score_j) =
+                play_match(&mut *players[i](), &mut *players[j](), round_count);
+
+            standings[i].score += score_i;
+            standings[j].score += score_j;
+            if score_i > score_j {
+                standings[i].wins += 1;
+                standings[j].losses += 1;
+            } else if score_i < score_j {
+                standings[i].losses += 1;
+                standings[j].wins += 1;
+            } else {
+                standings[i].draws += 1;
+                standings[j].draws += 1;
+            }
+        }
+    }
+
+    standings.sort_by_key(|standing| {
+        (
+            std::cmp::Reverse(standing.wins),
+            std::cmp::Reverse(standing.draws),
+            std::cmp::Reverse(standing.score),
+        )
+    });
+    standings
+}
+
+pub fn render_league_table(standings: &[Standing]) -> String {
+    let name_width = standings
+        .iter()
+        .map(|standing| standing.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("player".len());
+
+    let mut table = format!(
+        "{:>2}  {:<name_width$}  {:>3}  {:>3}  {:>3}  {:>8}\n",
+        "#",
+        "player",
+        "W",
+        "D",
+        "L",
+        "score",
+        name_width = name_width
+    );
+    for (rank, standing) in standings.iter().enumerate() {
+        writeln!(
+            table,
+            "{:>2}  {:<name_width$}  {:>3}  {:>3}  {:>3}  {:>8}",
+            rank + 1,
+            standing.name,
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.score,
+            name_width = name_width
+        )
+        .unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{play_match, run_round_robin, StrategyFactory};
+    use crate::{
+        strategy::{Copycat, FixedGuide, FrequencyCounter},
+        Move,
+    };
+
+    #[test]
+    fn both_sides_of_a_match_are_scored() {
+        let mut rock = FixedGuide::new(vec![Move::Rock]);
+        let mut paper = FixedGuide::new(vec![Move::Paper]);
+
+        let scores = play_match(&mut rock, &mut paper, 10);
+
+        assert_eq!(scores, (10, 80));
+    }
+
+    #[test]
+    fn copycat_is_always_one_step_behind_a_cycling_guide() {
+        let mut guide = FixedGuide::new(vec![Move::Rock, Move::Paper, Move::Scissors]);
+
+        let (guide_score, copycat_score) = play_match(&mut guide, &mut Copycat, 4);
+
+        assert!(guide_score > copycat_score);
+    }
+
+    #[test]
+    fn round_robin_plays_every_pairing_once() {
+        let players: Vec<StrategyFactory> = vec![
+            Box::new(|| Box::new(FixedGuide::new(vec![Move::Rock]))),
+            Box::new(|| Box::new(FrequencyCounter::new())),
+            Box::new(|| Box::new(Copycat)),
+        ];
+
+        let standings = run_round_robin(&players, 100);
+
+        assert!(standings
+            .iter()
+            .all(|standing| standing.wins + standing.draws + standing.losses == 2));
+        assert_eq!(standings[0].name, "frequency counter");
+        assert_eq!(standings[0].wins, 2);
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crate::find_common_item_type;
+
+const ITEM_TYPES: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const MIN_RUCKSACK_LEN: u64 = 16;
+const MAX_RUCKSACK_LEN: u64 = 48;
+// Every elf needs at least one item type besides the badge.
+pub const GROUP_SIZES: RangeInclusive<usize> = 2..=51;
+
+pub struct BenchmarkResult {
+    pub rucksack_count: usize,
+    pub group_size: usize,
+    pub search: Duration,
+    pub bitset: Duration,
+}
+
+// The approach `find_common_item_type` replaced: look every item type of the
+// first rucksack up in all the others.
+fn find_common_item_type_by_search(elf_group: &[String]) -> char {
+    let (first, others) = elf_group.split_first().unwrap();
+
+    for item_type in first.chars() {
+        if others.iter().all(|other| other.find(item_type).is_some()) {
+            return item_type;
+        }
+    }
+
+    panic!("No common item found in {:?}", elf_group)
+}
+
+// xorshift64, only used to make up rucksacks.
+struct Generator(u64);
+
+impl Generator {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
+
+// Each group gets a badge and every elf of the group draws its other items
+// from its own share of the remaining item types, so the badge is the only
+// item type the group has in common.
+pub fn generate_elf_groups(group_count: usize, group_size: usize, seed: u64) -> Vec<Vec<String>> {
+    assert!(
+        GROUP_SIZES.contains(&group_size),
+        "group size must be between 2 and 51"
+    );
+    let mut generator = Generator(seed.max(1));
+
+    (0..group_count)
+        .map(|_| {
+            let badge = generator.next(52) as usize;
+            let others: Vec<u8> = (0..52)
+                .filter(|&i| i != badge)
+                .map(|i| ITEM_TYPES[i])
+                .collect();
+            let share = others.len() / group_size;
+
+            (0..group_size)
+                .map(|elf| {
+                    let pool = &others[elf * share..(elf + 1) * share];
+                    let len =
+                        MIN_RUCKSACK_LEN + generator.next(MAX_RUCKSACK_LEN - MIN_RUCKSACK_LEN + 1);
+                    let badge_position = generator.next(len);
+                    (0..len)
+                        .map(|i| match i == badge_position {
+                            true => ITEM_TYPES[badge] as char,
+                            false => pool[generator.next(pool.len() as u64) as usize] as char,
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn run_benchmark(rucksack_count: usize, group_size: usize, seed: u64) -> BenchmarkResult {
+    let groups = generate_elf_groups(rucksack_count / group_size, group_size, seed);
+
+    let start = Instant::now();
+    let by_search: Vec<_> = groups
+        .iter()
+        .map(|group| find_common_item_type_by_search(group))
+        .collect();
+    let search = start.elapsed();
+
+    let start = Instant::now();
+    let by_bitset: Vec<_> = groups
+        .iter()
+        .map(|group| find_common_item_type(group).unwrap())
+        .collect();
+    let bitset = start.elapsed();
+
+    assert_eq!(
+        by_search, by_bitset,
+        "both approaches must find the same badges"
+    );
+
+    BenchmarkResult {
+        rucksack_count: groups.len() * group_size,
+        group_size,
+        search,
+        bitset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_common_item_type_by_search, generate_elf_groups};
+    use crate::{find_common_item_type, item_set::ItemSet};
+
+    #[test]
+    fn generated_groups_share_exactly_one_item_type() {
+        let groups = generate_elf_groups(100, 4, 7);
+
+        for group in &groups {
+            let common = ItemSet::intersect_all(group.iter().map(String::as_str)).unwrap();
+            assert_eq!(common.len(), 1);
+        }
+    }
+
+    #[test]
+    fn both_approaches_find_the_same_badges() {
+        for group in generate_elf_groups(100, 3, 11) {
+            assert_eq!(
+                find_common_item_type_by_search(&group),
+                find_common_item_type(&group).unwrap()
+            );
+        }
+    }
+}
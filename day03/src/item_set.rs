@@ -0,0 +1,136 @@
+use std::ops::BitAnd;
+
+use crate::validation::RucksackErrorKind;
+
+// One bit per item type, bit 0 for 'a' up to bit 51 for 'Z', so that the
+// position of a bit is the priority of its item type minus one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const ALL: ItemSet = ItemSet((1 << 52) - 1);
+
+    pub fn from_items(items: &str) -> Result<ItemSet, RucksackErrorKind> {
+        let mut bits = 0;
+        for (i, byte) in items.bytes().enumerate() {
+            match ITEM_MASKS[byte as usize] {
+                0 => return Err(invalid_item_type(items, i)),
+                mask => bits |= mask,
+            }
+        }
+        Ok(ItemSet(bits))
+    }
+
+    pub fn intersect_all<'a>(
+        rucksacks: impl IntoIterator<Item = &'a str>,
+    ) -> Result<ItemSet, RucksackErrorKind> {
+        rucksacks
+            .into_iter()
+            .try_fold(ItemSet::ALL, |common, items| {
+                Ok(common & ItemSet::from_items(items)?)
+            })
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    // Item types come out by increasing priority.
+    pub fn item_types(self) -> impl Iterator<Item = char> {
+        (0..52)
+            .filter(move |bit| self.0 & (1 << bit) != 0)
+            .map(bit_item)
+    }
+
+    pub fn first(self) -> Option<char> {
+        match self.0 {
+            0 => None,
+            bits => Some(bit_item(bits.trailing_zeros())),
+        }
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+// Indexed by byte so building a set is a single lookup per item; bytes that
+// aren't item types map to an empty mask.
+const ITEM_MASKS: [u64; 256] = {
+    let mut masks = [0; 256];
+    let mut bit = 0;
+    while bit < 26 {
+        masks[b'a' as usize + bit] = 1 << bit;
+        masks[b'A' as usize + bit] = 1 << (bit + 26);
+        bit += 1;
+    }
+    masks
+};
+
+// `index` is a byte offset, the column counts characters from 1.
+fn invalid_item_type(items: &str, index: usize) -> RucksackErrorKind {
+    let start = items.floor_char_boundary(index);
+    RucksackErrorKind::InvalidItemType {
+        column: items[..start].chars().count() + 1,
+        item_type: items[start..].chars().next().unwrap(),
+    }
+}
+
+fn bit_item(bit: u32) -> char {
+    match bit {
+        0..=25 => (b'a' + bit as u8) as char,
+        _ => (b'A' + (bit - 26) as u8) as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemSet;
+    use crate::validation::RucksackErrorKind;
+
+    #[test]
+    fn duplicate_item_types_are_counted_once() {
+        let set = ItemSet::from_items("aaBBz").unwrap();
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.item_types().collect::<String>(), "azB");
+    }
+
+    #[test]
+    fn intersection_of_any_number_of_rucksacks() {
+        let set = ItemSet::intersect_all(["abcZ", "bcZ", "xZcb", "Zb"]).unwrap();
+
+        assert_eq!(set.item_types().collect::<String>(), "bZ");
+        assert_eq!(set.first(), Some('b'));
+    }
+
+    #[test]
+    fn nothing_in_common() {
+        let set = ItemSet::from_items("abc").unwrap() & ItemSet::from_items("ABC").unwrap();
+
+        assert_eq!(set.len(), 0);
+        assert_eq!(set.first(), None);
+    }
+
+    #[test]
+    fn invalid_item_types_are_located_by_character() {
+        assert_eq!(
+            ItemSet::from_items("ab1c"),
+            Err(RucksackErrorKind::InvalidItemType {
+                column: 3,
+                item_type: '1'
+            })
+        );
+        assert_eq!(
+            ItemSet::from_items("aéb"),
+            Err(RucksackErrorKind::InvalidItemType {
+                column: 2,
+                item_type: 'é'
+            })
+        );
+    }
+}
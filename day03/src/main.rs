@@ -1,58 +1,400 @@
-use std::{env, fs};
+use std::{env, fs, process};
+
+use benchmark::{run_benchmark, GROUP_SIZES};
+use item_set::ItemSet;
+use regrouping::{parse_rucksacks, regroup};
+use reorganizer::reorganize;
+use validation::{RucksackError, RucksackErrorKind};
+
+mod benchmark;
+mod item_set;
+mod regrouping;
+mod reorganizer;
+mod validation;
 
 const LOWER_CASE_A_UTF16_INDEX: u32 = 97;
 const UPPER_CASE_A_UTF16_INDEX: u32 = 65;
 
-type ElfGroupRucksacks = [String; 3];
+const USAGE: &str = "\
+usage: day03 <path> [--mode compartments|badges] [--group-size N]
+       day03 <path> --reorganize
+       day03 <path> --regroup [--group-size N]
+       day03 --bench [--rucksacks N] [--group-size N]";
+const DEFAULT_GROUP_SIZE: usize = 3;
+const DEFAULT_BENCH_RUCKSACKS: usize = 3_000_000;
+const BENCH_SEED: u64 = 2022;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    // Item type found in both halves of each rucksack (part 1).
+    Compartments,
+    // Item type shared by each group of elves, three by default (part 2).
+    Badges,
+}
+
+struct Options {
+    path: Option<String>,
+    mode: Mode,
+    group_size: usize,
+    reorganize: bool,
+    regroup: bool,
+    bench: bool,
+    rucksacks: usize,
+}
 
 fn main() {
-    let input = read_file_from_args();
-    let elf_groups = parse_elf_groups(&input);
-    let common_item_types = find_common_item_types(&elf_groups);
-    let priority_sum = calculate_priority_sum(&common_item_types);
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_options(&args);
+
+    if options.bench {
+        let result = run_benchmark(options.rucksacks, options.group_size, BENCH_SEED);
+        println!(
+            "{} rucksacks in groups of {}",
+            result.rucksack_count, result.group_size
+        );
+        println!("search: {:?}", result.search);
+        println!("bitset: {:?}", result.bitset);
+        println!(
+            "speedup: {:.1}x",
+            result.search.as_secs_f64() / result.bitset.as_secs_f64()
+        );
+        return;
+    }
+
+    let mode = options.mode;
+    let input = fs::read_to_string(options.path.unwrap_or_else(|| panic!("{}", USAGE))).unwrap();
+
+    if options.reorganize {
+        let reorganization = reorganize(&input).unwrap_or_else(|errors| exit_with_errors(&errors));
+        for rucksack in &reorganization.rucksacks {
+            println!("{}", rucksack);
+        }
+
+        let swaps: usize = reorganization.fixed.iter().map(|(_, swaps)| swaps).sum();
+        eprintln!(
+            "fixed {} rucksack(s) with {} swap(s)",
+            reorganization.fixed.len(),
+            swaps
+        );
+        for line in &reorganization.unfixable {
+            eprintln!(
+                "line {}: can't be split into two compartments sharing nothing",
+                line
+            );
+        }
+        return;
+    }
+
+    if options.regroup {
+        let rucksacks = parse_rucksacks(&input).unwrap_or_else(|errors| exit_with_errors(&errors));
+        let Some(groups) = regroup(&rucksacks, options.group_size) else {
+            eprintln!(
+                "error: the {} rucksacks can't be split into groups of {} sharing exactly one item type",
+                rucksacks.len(),
+                options.group_size
+            );
+            process::exit(1)
+        };
+
+        let lines: Vec<&str> = input.lines().collect();
+        for group in &groups {
+            for line in &group.lines {
+                println!("{}", lines[line - 1]);
+            }
+            let line_numbers: Vec<_> = group.lines.iter().map(usize::to_string).collect();
+            eprintln!("lines {}: {}", line_numbers.join(", "), group.badge);
+        }
+        let badges: Vec<_> = groups.iter().map(|group| group.badge).collect();
+        eprintln!("priority sum: {}", calculate_priority_sum(&badges));
+        return;
+    }
+
+    let common_item_types = match mode {
+        Mode::Compartments => find_misplaced_item_types(&input),
+        Mode::Badges => find_common_item_types(&input, options.group_size),
+    };
+    let priority_sum = common_item_types
+        .map(|item_types| calculate_priority_sum(&item_types))
+        .unwrap_or_else(|errors| exit_with_errors(&errors));
 
     print!("{:?}", priority_sum)
 }
 
-fn read_file_from_args() -> String {
-    let args: Vec<String> = env::args().collect();
-    let path = args.get(1).unwrap();
-    fs::read_to_string(path).unwrap()
+fn parse_options(args: &[String]) -> Options {
+    let mut options = Options {
+        path: None,
+        mode: Mode::Badges,
+        group_size: DEFAULT_GROUP_SIZE,
+        reorganize: false,
+        regroup: false,
+        bench: false,
+        rucksacks: DEFAULT_BENCH_RUCKSACKS,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{}", USAGE));
+        match arg.as_str() {
+            "--mode" => options.mode = parse_mode(value()),
+            "--group-size" => options.group_size = value().parse().unwrap(),
+            "--reorganize" => options.reorganize = true,
+            "--regroup" => options.regroup = true,
+            "--bench" => options.bench = true,
+            "--rucksacks" => options.rucksacks = value().parse().unwrap(),
+            _ if options.path.is_none() && !arg.starts_with("--") => {
+                options.path = Some(arg.clone())
+            }
+            _ => panic!("{}", USAGE),
+        }
+    }
+
+    if options.group_size == 0 {
+        exit_with_usage_error("the group size must be at least 1");
+    }
+    if options.regroup && options.group_size == 1 {
+        exit_with_usage_error("regrouping needs groups of at least 2 rucksacks");
+    }
+    if options.bench && !GROUP_SIZES.contains(&options.group_size) {
+        exit_with_usage_error(&format!(
+            "the benchmark group size must be between {} and {}",
+            GROUP_SIZES.start(),
+            GROUP_SIZES.end()
+        ));
+    }
+    options
 }
 
-fn parse_elf_groups(input: &str) -> Vec<ElfGroupRucksacks> {
-    input
-        .lines()
-        .collect::<Vec<&str>>()
-        .chunks_exact(3)
-        .map(|elfs| [elfs[0], elfs[1], elfs[2]].map(String::from))
-        .collect()
+fn exit_with_usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2)
 }
 
-fn find_common_item_types(elf_groups: &Vec<ElfGroupRucksacks>) -> Vec<char> {
-    elf_groups.iter().map(find_common_item_type).collect()
+fn parse_mode(value: &str) -> Mode {
+    match value {
+        "compartments" => Mode::Compartments,
+        "badges" => Mode::Badges,
+        _ => panic!("{} is not a valid mode", value),
+    }
 }
 
-fn find_common_item_type(elf_group: &ElfGroupRucksacks) -> char {
-    let [elf1, elf2, elf3] = elf_group;
+// Every problem in the input is reported, not only the first one.
+fn exit_with_errors<T>(errors: &[RucksackError]) -> T {
+    eprintln!("found {} problem(s) in the rucksacks:", errors.len());
+    for err in errors {
+        eprintln!("  {}", err);
+    }
+    process::exit(1)
+}
 
-    for item_type in elf1.chars() {
-        if elf2.find(item_type).is_some() && elf3.find(item_type).is_some() {
-            return item_type;
+fn split_compartments(rucksack: &str) -> Result<(&str, &str), RucksackErrorKind> {
+    let length = rucksack.chars().count();
+    if !length.is_multiple_of(2) {
+        return Err(RucksackErrorKind::OddLength { length });
+    }
+
+    let middle = rucksack
+        .char_indices()
+        .nth(length / 2)
+        .map_or(rucksack.len(), |(i, _)| i);
+    Ok(rucksack.split_at(middle))
+}
+
+fn find_misplaced_item_types(input: &str) -> Result<Vec<char>, Vec<RucksackError>> {
+    let mut item_types = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, rucksack) in input.lines().enumerate() {
+        match find_misplaced_item_type(rucksack) {
+            Ok(item_type) => item_types.push(item_type),
+            Err(kind) => errors.push(kind.at_line(i + 1)),
         }
     }
 
-    panic!("No common item found in {:?}", elf_group)
+    match errors.is_empty() {
+        true => Ok(item_types),
+        false => Err(errors),
+    }
+}
+
+fn find_misplaced_item_type(rucksack: &str) -> Result<char, RucksackErrorKind> {
+    ItemSet::from_items(rucksack)?;
+    let (compartment1, compartment2) = split_compartments(rucksack)?;
+
+    single_item_type(ItemSet::intersect_all([compartment1, compartment2])?)
 }
 
-fn calculate_priority_sum(common_item_types: &Vec<char>) -> u32 {
+fn find_common_item_types(input: &str, group_size: usize) -> Result<Vec<char>, Vec<RucksackError>> {
+    let rucksacks: Vec<&str> = input.lines().collect();
+    let mut item_types = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, elf_group) in rucksacks.chunks(group_size).enumerate() {
+        let line = i * group_size + 1;
+        if elf_group.len() < group_size {
+            let kind = RucksackErrorKind::IncompleteGroup {
+                rucksacks: elf_group.len(),
+                group_size,
+            };
+            errors.push(kind.at_line(line));
+            continue;
+        }
+
+        // Invalid item types are pinned to their own line before the group
+        // as a whole is looked at.
+        let invalid: Vec<_> = (line..)
+            .zip(elf_group)
+            .filter_map(|(line, rucksack)| Some(ItemSet::from_items(rucksack).err()?.at_line(line)))
+            .collect();
+        if !invalid.is_empty() {
+            errors.extend(invalid);
+            continue;
+        }
+
+        match find_common_item_type(elf_group) {
+            Ok(item_type) => item_types.push(item_type),
+            Err(kind) => errors.push(kind.at_line(line)),
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(item_types),
+        false => Err(errors),
+    }
+}
+
+fn find_common_item_type<S: AsRef<str>>(elf_group: &[S]) -> Result<char, RucksackErrorKind> {
+    single_item_type(ItemSet::intersect_all(elf_group.iter().map(AsRef::as_ref))?)
+}
+
+fn single_item_type(common: ItemSet) -> Result<char, RucksackErrorKind> {
+    match common.len() {
+        0 => Err(RucksackErrorKind::NoSharedItemType),
+        1 => Ok(common.first().unwrap()),
+        _ => Err(RucksackErrorKind::SeveralSharedItemTypes {
+            item_types: common.item_types().collect(),
+        }),
+    }
+}
+
+// Only called with item types that made it through validation.
+fn calculate_priority_sum(common_item_types: &[char]) -> u32 {
     common_item_types.iter().map(convert_char_to_priority).sum()
 }
 
 fn convert_char_to_priority(c: &char) -> u32 {
-    if c.is_lowercase() {
-        return c.clone() as u32 - LOWER_CASE_A_UTF16_INDEX + 1;
-    } else {
-        return c.clone() as u32 - UPPER_CASE_A_UTF16_INDEX + 27;
+    match c {
+        'a'..='z' => *c as u32 - LOWER_CASE_A_UTF16_INDEX + 1,
+        'A'..='Z' => *c as u32 - UPPER_CASE_A_UTF16_INDEX + 27,
+        _ => panic!("{:?} is not an item type", c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        calculate_priority_sum, find_common_item_types, find_misplaced_item_types,
+        validation::{RucksackError, RucksackErrorKind},
+    };
+
+    const EXAMPLE_INPUT: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn compartments_of_the_example_share_these_item_types() {
+        let item_types = find_misplaced_item_types(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(item_types, vec!['p', 'L', 'P', 'v', 't', 's']);
+        assert_eq!(calculate_priority_sum(&item_types), 157);
+    }
+
+    #[test]
+    fn badges_of_the_example_sum_to_70() {
+        let item_types = find_common_item_types(EXAMPLE_INPUT, 3).unwrap();
+
+        assert_eq!(item_types, vec!['r', 'Z']);
+        assert_eq!(calculate_priority_sum(&item_types), 70);
+    }
+
+    #[test]
+    fn badges_of_larger_groups() {
+        let input = "abcD\nDxyz\nqDrs\nDDtu\nLmno\npLqr\nstLu\nLLvw";
+        let item_types = find_common_item_types(input, 4).unwrap();
+
+        assert_eq!(item_types, vec!['D', 'L']);
+        assert_eq!(calculate_priority_sum(&item_types), 30 + 38);
+    }
+
+    #[test]
+    fn odd_length_rucksacks_can_not_be_split() {
+        let result = find_misplaced_item_types("abca\nabc");
+
+        assert_eq!(
+            result,
+            Err(vec![RucksackError {
+                line: 2,
+                kind: RucksackErrorKind::OddLength { length: 3 }
+            }])
+        );
+    }
+
+    #[test]
+    fn every_problem_is_reported_with_its_line() {
+        let input = "abcD\nDx1z\nqDrs\nabcd\nefgh\nijkl\nabcd\nabce\nxyab\nabXY";
+        let errors = find_common_item_types(input, 3).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                RucksackError {
+                    line: 2,
+                    kind: RucksackErrorKind::InvalidItemType {
+                        column: 3,
+                        item_type: '1'
+                    }
+                },
+                RucksackError {
+                    line: 4,
+                    kind: RucksackErrorKind::NoSharedItemType
+                },
+                RucksackError {
+                    line: 7,
+                    kind: RucksackErrorKind::SeveralSharedItemTypes {
+                        item_types: "ab".to_string()
+                    }
+                },
+                RucksackError {
+                    line: 10,
+                    kind: RucksackErrorKind::IncompleteGroup {
+                        rucksacks: 1,
+                        group_size: 3
+                    }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn compartments_sharing_nothing_or_too_much() {
+        let errors = find_misplaced_item_types("abcd\nabab\nab-a").unwrap_err();
+        let kinds: Vec<_> = errors.into_iter().map(|err| err.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                RucksackErrorKind::NoSharedItemType,
+                RucksackErrorKind::SeveralSharedItemTypes {
+                    item_types: "ab".to_string()
+                },
+                RucksackErrorKind::InvalidItemType {
+                    column: 3,
+                    item_type: '-'
+                },
+            ]
+        );
     }
 }
@@ -0,0 +1,219 @@
+use crate::{item_set::ItemSet, validation::RucksackError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BadgeGroup {
+    // 1-based line numbers of the rucksacks in the group.
+    pub lines: Vec<usize>,
+    pub badge: char,
+}
+
+// Counting the groups a rucksack can still form stops here: only telling the
+// rucksacks with few of them from the others matters.
+const COUNT_LIMIT: usize = 50;
+
+struct Search<'a> {
+    rucksacks: &'a [ItemSet],
+    group_size: usize,
+    used: Vec<bool>,
+    chosen: Vec<Vec<usize>>,
+}
+
+impl Search<'_> {
+    // Groups `rucksack` could still form with the unassigned rucksacks, up to
+    // COUNT_LIMIT of them.
+    fn count_groups(&self, rucksack: usize) -> usize {
+        fn count(
+            search: &Search,
+            size: usize,
+            common: ItemSet,
+            start: usize,
+            exclude: usize,
+            limit: usize,
+        ) -> usize {
+            if size == search.group_size {
+                return (common.len() == 1) as usize;
+            }
+            let mut found = 0;
+            for next in start..search.rucksacks.len() {
+                if search.rucksacks.len() - next < search.group_size - size {
+                    break;
+                }
+                let narrowed = common & search.rucksacks[next];
+                if next == exclude || search.used[next] || narrowed.len() == 0 {
+                    continue;
+                }
+                found += count(search, size + 1, narrowed, next + 1, exclude, limit - found);
+                if found >= limit {
+                    break;
+                }
+            }
+            found
+        }
+
+        count(self, 1, self.rucksacks[rucksack], 0, rucksack, COUNT_LIMIT)
+    }
+
+    // The rucksack with the fewest groups left is placed first, so dead ends
+    // show up as early as possible, and it is grouped with the rucksacks that
+    // have the fewest groups themselves before the ones that fit anywhere.
+    fn solve(&mut self) -> bool {
+        let mut order: Vec<(usize, usize)> = (0..self.rucksacks.len())
+            .filter(|&i| !self.used[i])
+            .map(|i| (self.count_groups(i), i))
+            .collect();
+        order.sort_unstable();
+        match order.first() {
+            None => return true,
+            Some((0, _)) => return false,
+            Some(_) => {}
+        }
+        let order: Vec<usize> = order.into_iter().map(|(_, i)| i).collect();
+        let (&rucksack, partners) = order.split_first().unwrap();
+
+        self.used[rucksack] = true;
+        let found = self.extend(&mut vec![rucksack], self.rucksacks[rucksack], partners);
+        if !found {
+            self.used[rucksack] = false;
+        }
+        found
+    }
+
+    // Adding a rucksack can only shrink the shared item types, so a partial
+    // group that already shares nothing is abandoned.
+    fn extend(&mut self, group: &mut Vec<usize>, common: ItemSet, partners: &[usize]) -> bool {
+        if group.len() == self.group_size {
+            if common.len() != 1 {
+                return false;
+            }
+            self.chosen.push(group.clone());
+            if self.solve() {
+                return true;
+            }
+            self.chosen.pop();
+            return false;
+        }
+
+        let missing = self.group_size - group.len();
+        for (i, &next) in partners.iter().enumerate() {
+            if partners.len() - i < missing {
+                break;
+            }
+            let narrowed = common & self.rucksacks[next];
+            if narrowed.len() == 0 {
+                continue;
+            }
+
+            self.used[next] = true;
+            group.push(next);
+            if self.extend(group, narrowed, &partners[i + 1..]) {
+                return true;
+            }
+            group.pop();
+            self.used[next] = false;
+        }
+        false
+    }
+}
+
+pub fn regroup(rucksacks: &[ItemSet], group_size: usize) -> Option<Vec<BadgeGroup>> {
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return None;
+    }
+
+    let mut search = Search {
+        rucksacks,
+        group_size,
+        used: vec![false; rucksacks.len()],
+        chosen: Vec::new(),
+    };
+    if !search.solve() {
+        return None;
+    }
+
+    let mut groups: Vec<_> = search
+        .chosen
+        .into_iter()
+        .map(|mut members| {
+            members.sort_unstable();
+            let common = members
+                .iter()
+                .fold(ItemSet::ALL, |common, &i| common & rucksacks[i]);
+            BadgeGroup {
+                lines: members.iter().map(|i| i + 1).collect(),
+                badge: common.first().unwrap(),
+            }
+        })
+        .collect();
+    groups.sort_by_key(|group| group.lines[0]);
+    Some(groups)
+}
+
+pub fn parse_rucksacks(input: &str) -> Result<Vec<ItemSet>, Vec<RucksackError>> {
+    let mut rucksacks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line, rucksack) in (1..).zip(input.lines()) {
+        match ItemSet::from_items(rucksack) {
+            Ok(items) => rucksacks.push(items),
+            Err(kind) => errors.push(kind.at_line(line)),
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(rucksacks),
+        false => Err(errors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_rucksacks, regroup, BadgeGroup};
+    use crate::calculate_priority_sum;
+
+    #[test]
+    fn shuffled_example_groups_are_found_again() {
+        let input = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+ttgJtRGJQctTZtZT
+PmmdzqPrVvPwwTWBwg
+CrZsJsPPZsGzwwsLwLmpwMDw";
+        let groups = regroup(&parse_rucksacks(input).unwrap(), 3).unwrap();
+
+        assert_eq!(
+            groups,
+            vec![
+                BadgeGroup {
+                    lines: vec![1, 3, 5],
+                    badge: 'r'
+                },
+                BadgeGroup {
+                    lines: vec![2, 4, 6],
+                    badge: 'Z'
+                },
+            ]
+        );
+        let badges: Vec<_> = groups.iter().map(|group| group.badge).collect();
+        assert_eq!(calculate_priority_sum(&badges), 70);
+    }
+
+    #[test]
+    fn backtracks_out_of_a_greedy_first_group() {
+        // Every group of the first rucksack sharing 'a' leaves a 'b' rucksack
+        // behind with nothing in common with the others.
+        let input = "ab\nac\nad\nae\nby\nbz";
+        let groups = regroup(&parse_rucksacks(input).unwrap(), 3).unwrap();
+
+        let lines: Vec<_> = groups.iter().map(|group| group.lines.clone()).collect();
+        assert_eq!(lines, vec![vec![1, 5, 6], vec![2, 3, 4]]);
+    }
+
+    #[test]
+    fn no_partition_exists() {
+        let rucksacks = parse_rucksacks("ab\ncd\nef").unwrap();
+
+        assert_eq!(regroup(&rucksacks, 3), None);
+        assert_eq!(regroup(&rucksacks[..2], 3), None);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{item_set::ItemSet, split_compartments, validation::RucksackError};
+
+// Positions are character indices into the whole rucksack, the first one in
+// the first compartment and the second one in the second compartment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swap {
+    pub first: usize,
+    pub second: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reorganization {
+    pub rucksacks: Vec<String>,
+    // (line, number of swaps) of every rucksack that had to be changed.
+    pub fixed: Vec<(usize, usize)>,
+    pub unfixable: Vec<usize>,
+}
+
+// Each item type has to end up entirely in one compartment. Picking the item
+// types that stay in the first compartment is a subset sum over their counts
+// (they must fill exactly half the rucksack) in which every item type sent to
+// the second compartment costs one swap per item it has in the first one.
+pub fn plan_swaps(compartment1: &str, compartment2: &str) -> Option<Vec<Swap>> {
+    let first: Vec<char> = compartment1.chars().collect();
+    let second: Vec<char> = compartment2.chars().collect();
+    let half = first.len();
+
+    let mut item_types: Vec<char> = first.iter().chain(&second).copied().collect();
+    item_types.sort_unstable();
+    item_types.dedup();
+    let count = |items: &[char], item_type: char| items.iter().filter(|&&c| c == item_type).count();
+
+    // cost[i][s]: fewest items to move out of the first compartment when the
+    // first i item types put s items in it.
+    let mut cost = vec![vec![None; half + 1]; item_types.len() + 1];
+    cost[0][0] = Some(0);
+    for (i, &item_type) in item_types.iter().enumerate() {
+        let in_first = count(&first, item_type);
+        let total = in_first + count(&second, item_type);
+        for size in 0..=half {
+            let moved_out = cost[i][size].map(|c: usize| c + in_first);
+            let kept = size.checked_sub(total).and_then(|rest| cost[i][rest]);
+            cost[i + 1][size] = match (moved_out, kept) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+        }
+    }
+    cost[item_types.len()][half]?;
+
+    let mut keep_in_first = Vec::new();
+    let mut size = half;
+    for (i, &item_type) in item_types.iter().enumerate().rev() {
+        let in_first = count(&first, item_type);
+        let total = in_first + count(&second, item_type);
+        let moved_out = cost[i][size].map(|c| c + in_first);
+        if moved_out != cost[i + 1][size] {
+            keep_in_first.push(item_type);
+            size -= total;
+        }
+    }
+
+    let outgoing = (0..half).filter(|&i| !keep_in_first.contains(&first[i]));
+    let incoming = (0..half).filter(|&i| keep_in_first.contains(&second[i]));
+    Some(
+        outgoing
+            .zip(incoming)
+            .map(|(i, j)| Swap {
+                first: i,
+                second: half + j,
+            })
+            .collect(),
+    )
+}
+
+pub fn apply_swaps(rucksack: &str, swaps: &[Swap]) -> String {
+    let mut items: Vec<char> = rucksack.chars().collect();
+    for swap in swaps {
+        items.swap(swap.first, swap.second);
+    }
+    items.into_iter().collect()
+}
+
+// Rucksacks that can't be fixed are kept as they are so the output still
+// lines up with the input.
+pub fn reorganize(input: &str) -> Result<Reorganization, Vec<RucksackError>> {
+    let mut reorganization = Reorganization {
+        rucksacks: Vec::new(),
+        fixed: Vec::new(),
+        unfixable: Vec::new(),
+    };
+    let mut errors = Vec::new();
+
+    for (line, rucksack) in (1..).zip(input.lines()) {
+        let compartments = ItemSet::from_items(rucksack).and_then(|_| split_compartments(rucksack));
+        let (compartment1, compartment2) = match compartments {
+            Ok(compartments) => compartments,
+            Err(kind) => {
+                errors.push(kind.at_line(line));
+                continue;
+            }
+        };
+
+        match plan_swaps(compartment1, compartment2) {
+            Some(swaps) => {
+                if !swaps.is_empty() {
+                    reorganization.fixed.push((line, swaps.len()));
+                }
+                reorganization.rucksacks.push(apply_swaps(rucksack, &swaps));
+            }
+            None => {
+                reorganization.unfixable.push(line);
+                reorganization.rucksacks.push(rucksack.to_string());
+            }
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(reorganization),
+        false => Err(errors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_swaps, plan_swaps, reorganize, Swap};
+    use crate::{item_set::ItemSet, split_compartments};
+
+    fn compartments_share_nothing(rucksack: &str) -> bool {
+        let (compartment1, compartment2) = split_compartments(rucksack).unwrap();
+        ItemSet::intersect_all([compartment1, compartment2])
+            .unwrap()
+            .len()
+            == 0
+    }
+
+    #[test]
+    fn one_shared_item_type_takes_one_swap() {
+        let swaps = plan_swaps("vJrwpWtwJgWr", "hcsFMMfFFhFp").unwrap();
+
+        assert_eq!(
+            swaps,
+            vec![Swap {
+                first: 0,
+                second: 23
+            }]
+        );
+        let fixed = apply_swaps("vJrwpWtwJgWrhcsFMMfFFhFp", &swaps);
+        assert!(compartments_share_nothing(&fixed));
+    }
+
+    #[test]
+    fn keeping_the_item_types_that_fill_half_with_the_fewest_moves() {
+        // The first compartment can only hold all three 'b's plus the 'a' or
+        // the 'c'. Either way one item leaves it, and the same swap brings in
+        // the only 'b' of the second compartment. Keeping the 'd's instead
+        // would move three items out.
+        let swaps = plan_swaps("abbc", "bddd").unwrap();
+        let reorganized = apply_swaps("abbcbddd", &swaps);
+
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(reorganized[..4].matches('b').count(), 3);
+        assert!(compartments_share_nothing(&reorganized));
+    }
+
+    #[test]
+    fn rucksacks_that_can_not_be_split_by_item_type() {
+        assert_eq!(plan_swaps("aa", "ab"), None);
+    }
+
+    #[test]
+    fn the_example_is_fixed_line_by_line() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabcd\naaab";
+        let reorganization = reorganize(input).unwrap();
+
+        assert_eq!(reorganization.fixed, vec![(1, 1)]);
+        assert_eq!(reorganization.unfixable, vec![3]);
+        assert_eq!(reorganization.rucksacks[1], "abcd");
+        assert_eq!(reorganization.rucksacks[2], "aaab");
+        assert!(compartments_share_nothing(&reorganization.rucksacks[0]));
+    }
+}
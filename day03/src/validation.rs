@@ -0,0 +1,58 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RucksackErrorKind {
+    InvalidItemType { column: usize, item_type: char },
+    OddLength { length: usize },
+    IncompleteGroup { rucksacks: usize, group_size: usize },
+    NoSharedItemType,
+    SeveralSharedItemTypes { item_types: String },
+}
+
+// Problems with a whole group are reported on the group's first line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RucksackError {
+    pub line: usize,
+    pub kind: RucksackErrorKind,
+}
+
+impl Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            RucksackErrorKind::InvalidItemType { column, item_type } => write!(
+                f,
+                "column {}: {:?} is not an item type (expected a-z or A-Z)",
+                column, item_type
+            ),
+            RucksackErrorKind::OddLength { length } => write!(
+                f,
+                "a rucksack of {} items can't be split into two equal compartments",
+                length
+            ),
+            RucksackErrorKind::IncompleteGroup {
+                rucksacks,
+                group_size,
+            } => write!(
+                f,
+                "the last group only has {} of {} rucksacks",
+                rucksacks, group_size
+            ),
+            RucksackErrorKind::NoSharedItemType => write!(f, "no item type is shared"),
+            RucksackErrorKind::SeveralSharedItemTypes { item_types } => {
+                write!(f, "several item types are shared: {}", item_types)
+            }
+        }
+    }
+}
+
+impl Error for RucksackError {}
+
+impl RucksackErrorKind {
+    pub fn at_line(self, line: usize) -> RucksackError {
+        RucksackError { line, kind: self }
+    }
+}
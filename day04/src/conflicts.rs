@@ -0,0 +1,115 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::RangeInclusive};
+
+use crate::parsing::LineAssignment;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfAssignment {
+    pub line: usize,
+    // 1-based position of the elf on its line.
+    pub elf: usize,
+    pub sections: RangeInclusive<u32>,
+}
+
+// Edges of the conflict graph, as indices into the assignments. The first
+// index is always the assignment starting first.
+pub type Conflict = (usize, usize);
+
+// Assignments are swept by start section while the ones still running are
+// kept in a min-heap by end section. Whatever is left in the heap when an
+// assignment starts overlaps it, so each heap entry looked at is a conflict
+// and the whole sweep is O(n log n + k) for k conflicts.
+pub fn find_conflicts(assignments: &[ElfAssignment]) -> Vec<Conflict> {
+    let mut order: Vec<usize> = (0..assignments.len())
+        .filter(|&i| !assignments[i].sections.is_empty())
+        .collect();
+    order.sort_by_key(|&i| *assignments[i].sections.start());
+
+    let mut running: BinaryHeap<Reverse<(u32, usize)>> = BinaryHeap::new();
+    let mut conflicts = Vec::new();
+
+    for i in order {
+        let sections = &assignments[i].sections;
+        while let Some(&Reverse((end, _))) = running.peek() {
+            if end >= *sections.start() {
+                break;
+            }
+            running.pop();
+        }
+
+        let mut overlapping: Vec<usize> = running.iter().map(|Reverse((_, j))| *j).collect();
+        overlapping.sort_unstable();
+        conflicts.extend(overlapping.into_iter().map(|j| (j, i)));
+
+        running.push(Reverse((*sections.end(), i)));
+    }
+    conflicts
+}
+
+pub fn flatten_assignments(assignments: &[LineAssignment]) -> Vec<ElfAssignment> {
+    (1..)
+        .zip(assignments)
+        .flat_map(|(line, ranges)| {
+            ranges
+                .iter()
+                .enumerate()
+                .map(move |(i, sections)| ElfAssignment {
+                    line,
+                    elf: i + 1,
+                    sections: sections.clone(),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_conflicts, flatten_assignments, Conflict, ElfAssignment};
+    use crate::test_util::random_numbers;
+
+    fn find_conflicts_pairwise(assignments: &[ElfAssignment]) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for i in 0..assignments.len() {
+            for j in i + 1..assignments.len() {
+                let (a, b) = (&assignments[i].sections, &assignments[j].sections);
+                if a.start().max(b.start()) <= a.end().min(b.end()) {
+                    conflicts.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+        conflicts.sort_unstable();
+        conflicts
+    }
+
+    #[test]
+    fn conflicts_across_lines() {
+        let assignments = flatten_assignments(&[vec![2..=4, 6..=8], vec![2..=3, 4..=5]]);
+
+        let conflicts = find_conflicts(&assignments);
+
+        // 2-4 overlaps 2-3 and 4-5, the other elves only meet their partner.
+        assert_eq!(conflicts, vec![(0, 2), (0, 3)]);
+        assert_eq!(assignments[3].line, 2);
+        assert_eq!(assignments[3].elf, 2);
+    }
+
+    #[test]
+    fn sweep_finds_the_same_conflicts_as_comparing_every_pair() {
+        let mut next = random_numbers(4);
+        let pairs: Vec<_> = (0..300)
+            .map(|_| {
+                let (a, b) = (next(500), next(500));
+                let (c, d) = (next(500), next(500));
+                vec![a.min(b)..=a.max(b) / 8 + a.min(b), c..=c + d / 50]
+            })
+            .collect();
+        let assignments = flatten_assignments(&pairs);
+
+        let mut conflicts: Vec<_> = find_conflicts(&assignments)
+            .into_iter()
+            .map(|(i, j)| (i.min(j), i.max(j)))
+            .collect();
+        conflicts.sort_unstable();
+
+        assert_eq!(conflicts, find_conflicts_pairwise(&assignments));
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+// Sorted, non-overlapping ranges with at least one section between two
+// consecutive ranges; overlapping or adjacent ranges are merged on the way in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl IntervalSet {
+    // Empty ranges, like a reversed `7..=3`, add nothing.
+    pub fn from_ranges(ranges: impl IntoIterator<Item = RangeInclusive<u32>>) -> IntervalSet {
+        let mut ranges: Vec<_> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|range| *range.start());
+
+        let mut merged: Vec<RangeInclusive<u32>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if last.end().saturating_add(1) >= *range.start() => {
+                    *last = *last.start()..=*last.end().max(range.end());
+                }
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_ranges(self.ranges.iter().chain(&other.ranges).cloned())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                ranges.push(start..=end);
+            }
+            // The range ending first can't meet anything further on.
+            match a.end() < b.end() {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        self.intersection(&other.complement())
+    }
+
+    // Sections within `bounds` that aren't in the set.
+    pub fn gaps(&self, bounds: RangeInclusive<u32>) -> IntervalSet {
+        IntervalSet::from_ranges([bounds]).difference(self)
+    }
+
+    pub fn covered_length(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| (range.end() - range.start()) as u64 + 1)
+            .sum()
+    }
+
+    // The smallest range holding every section of the set.
+    pub fn hull(&self) -> Option<RangeInclusive<u32>> {
+        let first = self.ranges.first()?;
+        let last = self.ranges.last()?;
+        Some(*first.start()..=*last.end())
+    }
+
+    fn complement(&self) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let mut next = Some(0);
+
+        for range in &self.ranges {
+            let Some(start) = next else { break };
+            if start < *range.start() {
+                ranges.push(start..=range.start() - 1);
+            }
+            next = range.end().checked_add(1);
+        }
+        if let Some(start) = next {
+            ranges.push(start..=u32::MAX);
+        }
+        IntervalSet { ranges }
+    }
+}
+
+// Written the way the puzzle writes assignments: `2-4,6-8`.
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges: Vec<_> = self
+            .ranges
+            .iter()
+            .map(|range| format!("{}-{}", range.start(), range.end()))
+            .collect();
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::IntervalSet;
+
+    #[test]
+    fn overlapping_and_adjacent_ranges_are_merged() {
+        let set = IntervalSet::from_ranges([6..=8, 2..=4, 5..=5, 12..=14, 13..=20]);
+
+        assert_eq!(set.to_string(), "2-8,12-20");
+        assert_eq!(set.covered_length(), 16);
+    }
+
+    #[test]
+    fn reversed_ranges_are_empty() {
+        let set = IntervalSet::from_ranges([RangeInclusive::new(7, 3)]);
+
+        assert_eq!(set, IntervalSet::default());
+        assert_eq!(set.hull(), None);
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a = IntervalSet::from_ranges([2..=8, 12..=15]);
+        let b = IntervalSet::from_ranges([5..=13]);
+
+        assert_eq!(a.union(&b).to_string(), "2-15");
+        assert_eq!(a.intersection(&b).to_string(), "5-8,12-13");
+        assert_eq!(a.difference(&b).to_string(), "2-4,14-15");
+        assert_eq!(b.difference(&a).to_string(), "9-11");
+    }
+
+    #[test]
+    fn gaps_within_bounds() {
+        let set = IntervalSet::from_ranges([2..=4, 6..=8]);
+
+        assert_eq!(set.gaps(1..=9).to_string(), "1-1,5-5,9-9");
+        assert_eq!(set.gaps(3..=7).to_string(), "5-5");
+    }
+
+    #[test]
+    fn ranges_reaching_the_end_of_u32() {
+        let set = IntervalSet::from_ranges([0..=3, 10..=u32::MAX]);
+
+        assert_eq!(set.gaps(0..=u32::MAX).to_string(), "4-9");
+        assert_eq!(set.covered_length(), 4 + (u32::MAX as u64 - 9));
+    }
+}
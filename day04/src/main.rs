@@ -1,52 +1,288 @@
-use std::{env, fs, ops::RangeInclusive};
+use std::{
+    env, fs,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    process,
+};
+
+use conflicts::{find_conflicts, flatten_assignments, ElfAssignment};
+use interval_set::IntervalSet;
+use parsing::{parse_assignments, LineAssignment, ReversedRanges};
+use predicates::{count_all_predicates, PairPredicate};
+use section_map::{render_coverage_strip, render_line_assignment, SectionView, SectionViewError};
+
+mod conflicts;
+mod interval_set;
+mod parsing;
+mod predicates;
+mod section_map;
+#[cfg(test)]
+mod test_util;
 
 type PairAssignment = (RangeInclusive<u32>, RangeInclusive<u32>);
 type PairAssignments = Vec<PairAssignment>;
 
-fn main() {
-    let input = read_file_from_args();
-    let pair_assignments = parse_pair_assignments(&input);
-    let matching_range = find_matching_ranges(&pair_assignments);
+const USAGE: &str = "\
+usage: day04 <path> [--normalize-reversed] [--predicate contains|overlaps|disjoint|adjacent|equal|all]
+       day04 <path> [--normalize-reversed] --coverage
+       day04 <path> [--normalize-reversed] --conflicts
+       day04 <path> [--normalize-reversed] --map";
+
+enum Command {
+    Count(PairPredicate),
+    CountAll,
+    Coverage,
+    Conflicts,
+    Map,
+}
 
-    println!("{:?}", matching_range.len());
+struct Options {
+    command: Command,
+    reversed: ReversedRanges,
 }
 
-fn read_file_from_args() -> String {
+// Sections are counted across the whole input, not within each pair.
+struct Coverage {
+    covered: IntervalSet,
+    double_covered: IntervalSet,
+}
+
+fn main() {
     let args: Vec<String> = env::args().collect();
-    let path = args.get(1).unwrap();
+    let input = read_file_from_args(&args);
+    let options = parse_options(&args[2..]);
+    let assignments = parse_assignments(&input, options.reversed).unwrap_or_else(exit_with_error);
+
+    match options.command {
+        Command::Count(predicate) => {
+            let matching_range = find_matching_ranges(&assignments, predicate);
+            println!("{:?}", matching_range.len());
+        }
+        Command::CountAll => {
+            let counts = count_all_predicates(&elf_pairs(&assignments));
+            for (predicate, count) in PairPredicate::ALL.iter().zip(counts) {
+                println!("{}: {}", predicate.name(), count);
+            }
+        }
+        Command::Coverage => print_coverage(&find_coverage(&assignments)),
+        Command::Conflicts => {
+            let elves = flatten_assignments(&assignments);
+            let conflicts = find_conflicts(&elves);
+            print_conflicts(&elves, &conflicts).unwrap();
+            eprintln!(
+                "{} elves, {} conflicting pair(s)",
+                elves.len(),
+                conflicts.len()
+            );
+        }
+        Command::Map => print_section_map(&assignments),
+    }
+}
+
+fn read_file_from_args(args: &[String]) -> String {
+    let path = args.get(1).unwrap_or_else(|| panic!("{}", USAGE));
     fs::read_to_string(path).unwrap()
 }
 
-fn parse_pair_assignments(input: &str) -> PairAssignments {
-    input.lines().map(parse_pair_assignment).collect()
+fn parse_options(args: &[String]) -> Options {
+    let mut options = Options {
+        command: Command::Count(PairPredicate::Overlaps),
+        reversed: ReversedRanges::Reject,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--normalize-reversed" => options.reversed = ReversedRanges::Normalize,
+            "--coverage" => options.command = Command::Coverage,
+            "--conflicts" => options.command = Command::Conflicts,
+            "--map" => options.command = Command::Map,
+            "--predicate" => {
+                let value = args.next().unwrap_or_else(|| panic!("{}", USAGE));
+                options.command = match value.as_str() {
+                    "all" => Command::CountAll,
+                    _ => Command::Count(
+                        PairPredicate::from_name(value)
+                            .unwrap_or_else(|| panic!("{} is not a valid predicate", value)),
+                    ),
+                }
+            }
+            _ => panic!("{}", USAGE),
+        }
+    }
+
+    options
 }
 
-fn parse_pair_assignment(line: &str) -> PairAssignment {
-    let pair = line
-        .split(',')
-        .map(|range| {
-            let range = range
-                .split('-')
-                .map(|n| n.parse::<u32>())
-                .collect::<Result<Vec<_>, _>>()
-                .unwrap();
+fn exit_with_error<T>(err: impl std::error::Error) -> T {
+    eprintln!("error: {}", err);
+    process::exit(1)
+}
 
-            RangeInclusive::new(range[0], range[1])
+// Every pair of elves sharing a line; a line with two elves is one pair.
+fn elf_pairs(assignments: &[LineAssignment]) -> PairAssignments {
+    assignments
+        .iter()
+        .flat_map(|ranges| {
+            ranges.iter().enumerate().flat_map(move |(i, range_1)| {
+                ranges[i + 1..]
+                    .iter()
+                    .map(move |range_2| (range_1.clone(), range_2.clone()))
+            })
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    (pair[0].clone(), pair[1].clone())
+fn find_matching_ranges(
+    assignments: &[LineAssignment],
+    predicate: PairPredicate,
+) -> PairAssignments {
+    elf_pairs(assignments)
+        .into_iter()
+        .filter(|(range_1, range_2)| predicate.holds(range_1, range_2))
+        .collect()
 }
 
-fn find_matching_ranges(pair_assignments: &PairAssignments) -> PairAssignments {
-    pair_assignments
+// Ranges are swept by start section: whatever part of a range starts before
+// the furthest end seen so far is covered by at least one earlier range.
+fn find_coverage(assignments: &[LineAssignment]) -> Coverage {
+    let mut ranges: Vec<_> = assignments
         .iter()
-        .filter(|(range_1, range_2)| overlap(range_1, range_2))
-        .map(|pair| pair.clone())
-        .collect()
+        .flatten()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect();
+    ranges.sort_by_key(|range| *range.start());
+
+    let mut overlaps = Vec::new();
+    let mut furthest_end: Option<u32> = None;
+    for range in &ranges {
+        if let Some(end) = furthest_end.filter(|end| end >= range.start()) {
+            overlaps.push(*range.start()..=end.min(*range.end()));
+        }
+        furthest_end = furthest_end.max(Some(*range.end()));
+    }
+
+    Coverage {
+        covered: IntervalSet::from_ranges(ranges),
+        double_covered: IntervalSet::from_ranges(overlaps),
+    }
+}
+
+fn print_coverage(coverage: &Coverage) {
+    let Some(hull) = coverage.covered.hull() else {
+        println!("no sections are assigned");
+        return;
+    };
+    let uncovered = coverage.covered.gaps(hull.clone());
+    let covered_once = coverage.covered.difference(&coverage.double_covered);
+
+    println!("sections {}-{}", hull.start(), hull.end());
+    println!("covered:        {}", coverage.covered.covered_length());
+    println!("covered once:   {}", covered_once.covered_length());
+    println!("double-covered: {}", describe(&coverage.double_covered));
+    println!("not covered:    {}", describe(&uncovered));
+    println!(
+        "to rebalance:   {}",
+        describe(&uncovered.union(&coverage.double_covered))
+    );
+}
+
+fn describe(sections: &IntervalSet) -> String {
+    match sections.covered_length() {
+        0 => "0".to_string(),
+        length => format!("{} ({})", length, sections),
+    }
 }
 
-fn overlap(range_1: &RangeInclusive<u32>, range_2: &RangeInclusive<u32>) -> bool {
-    (range_1.start() <= range_2.start() && range_2.start() <= range_1.end())
-        || (range_2.start() <= range_1.start() && range_1.start() <= range_2.end())
+// One edge of the conflict graph per line, each elf written as
+// `line:position` followed by its assignment.
+fn print_conflicts(assignments: &[ElfAssignment], conflicts: &[(usize, usize)]) -> io::Result<()> {
+    let describe = |elf: &ElfAssignment| {
+        format!(
+            "{}:{} {}-{}",
+            elf.line,
+            elf.elf,
+            elf.sections.start(),
+            elf.sections.end()
+        )
+    };
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    for &(i, j) in conflicts {
+        writeln!(
+            out,
+            "{} <-> {}",
+            describe(&assignments[i]),
+            describe(&assignments[j])
+        )?;
+    }
+    out.flush()
+}
+
+fn print_section_map(assignments: &[LineAssignment]) {
+    let view = match SectionView::for_assignments(assignments) {
+        Ok(view) => view,
+        Err(SectionViewError::NoSections) => {
+            println!("no sections are assigned");
+            return;
+        }
+        Err(err) => exit_with_error(err),
+    };
+
+    for ranges in assignments {
+        println!("{}\n", render_line_assignment(&view, ranges));
+    }
+    println!("{}  coverage", render_coverage_strip(&view, assignments));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_coverage, interval_set::IntervalSet, parsing::LineAssignment,
+        test_util::random_numbers,
+    };
+
+    // Straightforward but quadratic: every range is checked against
+    // everything covered before it.
+    fn find_coverage_by_folding(assignments: &[LineAssignment]) -> (IntervalSet, IntervalSet) {
+        let mut covered = IntervalSet::default();
+        let mut double_covered = IntervalSet::default();
+
+        for range in assignments.iter().flatten() {
+            let assignment = IntervalSet::from_ranges([range.clone()]);
+            double_covered = double_covered.union(&covered.intersection(&assignment));
+            covered = covered.union(&assignment);
+        }
+        (covered, double_covered)
+    }
+
+    #[test]
+    fn sweep_matches_folding_every_range_in() {
+        let mut next = random_numbers(9);
+        let assignments: Vec<LineAssignment> = (0..500)
+            .map(|_| {
+                (0..2)
+                    .map(|_| {
+                        let start = next(2000);
+                        start..=start + next(30)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let coverage = find_coverage(&assignments);
+
+        let (covered, double_covered) = find_coverage_by_folding(&assignments);
+        assert_eq!(coverage.covered, covered);
+        assert_eq!(coverage.double_covered, double_covered);
+    }
+
+    #[test]
+    fn ranges_nested_in_an_earlier_one() {
+        let coverage = find_coverage(&[vec![1..=10, 2..=3], vec![5..=6, 12..=12]]);
+
+        assert_eq!(coverage.covered.to_string(), "1-10,12-12");
+        assert_eq!(coverage.double_covered.to_string(), "2-3,5-6");
+    }
 }
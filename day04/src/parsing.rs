@@ -0,0 +1,170 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+    ops::RangeInclusive,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReversedRanges {
+    Reject,
+    // `7-3` is read as `3-7`.
+    Normalize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingDash,
+    InvalidSection(ParseIntError),
+    ReversedRange,
+    TooFewElves(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignmentParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for AssignmentParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::MissingDash => {
+                write!(f, "expected a range like 2-4, found {:?}", self.text)
+            }
+            ParseErrorKind::InvalidSection(err) => {
+                write!(f, "invalid section {:?} ({})", self.text, err)
+            }
+            ParseErrorKind::ReversedRange => write!(
+                f,
+                "range {:?} ends before it starts (use --normalize-reversed to accept it)",
+                self.text
+            ),
+            ParseErrorKind::TooFewElves(count) => write!(
+                f,
+                "expected at least two elves, found {} in {:?}",
+                count, self.text
+            ),
+        }
+    }
+}
+
+impl Error for AssignmentParseError {}
+
+// Every elf of the line, in order; a line has at least two of them.
+pub type LineAssignment = Vec<RangeInclusive<u32>>;
+
+pub fn parse_assignments(
+    input: &str,
+    reversed: ReversedRanges,
+) -> Result<Vec<LineAssignment>, AssignmentParseError> {
+    (1..)
+        .zip(input.lines())
+        .map(|(line_number, line)| parse_line_assignment(line_number, line, reversed))
+        .collect()
+}
+
+fn parse_line_assignment(
+    line_number: usize,
+    line: &str,
+    reversed: ReversedRanges,
+) -> Result<LineAssignment, AssignmentParseError> {
+    let error = |offset: usize, text: &str, kind| AssignmentParseError {
+        line: line_number,
+        column: line[..offset].chars().count() + 1,
+        text: text.to_string(),
+        kind,
+    };
+
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for field in line.split(',') {
+        let range = parse_range(field)
+            .map_err(|(start, end, kind)| error(offset + start, &field[start..end], kind))?;
+
+        ranges.push(match (range.is_empty(), reversed) {
+            (false, _) => range,
+            (true, ReversedRanges::Normalize) => *range.end()..=*range.start(),
+            (true, ReversedRanges::Reject) => {
+                let leading = field.len() - field.trim_start().len();
+                let kind = ParseErrorKind::ReversedRange;
+                return Err(error(offset + leading, field.trim(), kind));
+            }
+        });
+        offset += field.len() + 1;
+    }
+
+    if ranges.len() < 2 {
+        return Err(error(0, line, ParseErrorKind::TooFewElves(ranges.len())));
+    }
+    Ok(ranges)
+}
+
+// Errors come with the byte span of the offending text within `field`.
+fn parse_range(field: &str) -> Result<RangeInclusive<u32>, (usize, usize, ParseErrorKind)> {
+    let Some((start, end)) = field.split_once('-') else {
+        return Err((0, field.len(), ParseErrorKind::MissingDash));
+    };
+
+    let section = |text: &str, offset: usize| {
+        text.trim().parse::<u32>().map_err(|err| {
+            let leading = text.len() - text.trim_start().len();
+            let span = (offset + leading, offset + text.trim_end().len());
+            (
+                span.0,
+                span.1.max(span.0),
+                ParseErrorKind::InvalidSection(err),
+            )
+        })
+    };
+    Ok(section(start, 0)?..=section(end, start.len() + 1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_assignments, ParseErrorKind, ReversedRanges};
+
+    #[test]
+    fn example_lines_have_two_elves() {
+        let lines = parse_assignments("2-4,6-8\n2-3,4-5", ReversedRanges::Reject).unwrap();
+
+        assert_eq!(lines, vec![vec![2..=4, 6..=8], vec![2..=3, 4..=5]]);
+    }
+
+    #[test]
+    fn lines_can_have_more_than_two_elves() {
+        let lines = parse_assignments("1-2, 3-4 ,5-6", ReversedRanges::Reject).unwrap();
+
+        assert_eq!(lines, vec![vec![1..=2, 3..=4, 5..=6]]);
+    }
+
+    #[test]
+    fn reversed_ranges_are_rejected_or_normalized() {
+        let err = parse_assignments("2-4,6-8\n2-4,7-3", ReversedRanges::Reject).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.text, "7-3");
+        assert_eq!(err.kind, ParseErrorKind::ReversedRange);
+
+        let lines = parse_assignments("2-4,7-3", ReversedRanges::Normalize).unwrap();
+        assert_eq!(lines, vec![vec![2..=4, 3..=7]]);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let invalid = parse_assignments("2-4,6-x8", ReversedRanges::Reject).unwrap_err();
+        assert_eq!((invalid.column, invalid.text.as_str()), (7, "x8"));
+        assert!(matches!(invalid.kind, ParseErrorKind::InvalidSection(_)));
+
+        let dash = parse_assignments("2-4,68", ReversedRanges::Reject).unwrap_err();
+        assert_eq!((dash.column, dash.kind), (5, ParseErrorKind::MissingDash));
+
+        let single = parse_assignments("2-4", ReversedRanges::Reject).unwrap_err();
+        assert_eq!(
+            (single.column, single.kind),
+            (1, ParseErrorKind::TooFewElves(1))
+        );
+    }
+}
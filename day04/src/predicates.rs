@@ -0,0 +1,113 @@
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairPredicate {
+    // One range fully contains the other (part 1).
+    Contains,
+    // The ranges have at least one section in common (part 2).
+    Overlaps,
+    Disjoint,
+    // The ranges don't overlap but one starts right after the other ends.
+    Adjacent,
+    Equal,
+}
+
+impl PairPredicate {
+    pub const ALL: [PairPredicate; 5] = [
+        PairPredicate::Contains,
+        PairPredicate::Overlaps,
+        PairPredicate::Disjoint,
+        PairPredicate::Adjacent,
+        PairPredicate::Equal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PairPredicate::Contains => "contains",
+            PairPredicate::Overlaps => "overlaps",
+            PairPredicate::Disjoint => "disjoint",
+            PairPredicate::Adjacent => "adjacent",
+            PairPredicate::Equal => "equal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PairPredicate> {
+        PairPredicate::ALL
+            .into_iter()
+            .find(|predicate| predicate.name() == name)
+    }
+
+    pub fn holds(self, range_1: &RangeInclusive<u32>, range_2: &RangeInclusive<u32>) -> bool {
+        match self {
+            PairPredicate::Contains => contains(range_1, range_2) || contains(range_2, range_1),
+            PairPredicate::Overlaps => overlap(range_1, range_2),
+            PairPredicate::Disjoint => !overlap(range_1, range_2),
+            PairPredicate::Adjacent => {
+                range_1.end().checked_add(1) == Some(*range_2.start())
+                    || range_2.end().checked_add(1) == Some(*range_1.start())
+            }
+            PairPredicate::Equal => range_1 == range_2,
+        }
+    }
+}
+
+// Counts for every predicate, in the order of `PairPredicate::ALL`.
+pub fn count_all_predicates<'a>(
+    pairs: impl IntoIterator<Item = &'a (RangeInclusive<u32>, RangeInclusive<u32>)>,
+) -> [usize; 5] {
+    let mut counts = [0; 5];
+    for (range_1, range_2) in pairs {
+        for (count, predicate) in counts.iter_mut().zip(PairPredicate::ALL) {
+            if predicate.holds(range_1, range_2) {
+                *count += 1;
+            }
+        }
+    }
+    counts
+}
+
+fn contains(outer: &RangeInclusive<u32>, inner: &RangeInclusive<u32>) -> bool {
+    outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
+fn overlap(range_1: &RangeInclusive<u32>, range_2: &RangeInclusive<u32>) -> bool {
+    (range_1.start() <= range_2.start() && range_2.start() <= range_1.end())
+        || (range_2.start() <= range_1.start() && range_1.start() <= range_2.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::{count_all_predicates, PairPredicate};
+
+    const EXAMPLE_PAIRS: [(RangeInclusive<u32>, RangeInclusive<u32>); 6] = [
+        (2..=4, 6..=8),
+        (2..=3, 4..=5),
+        (5..=7, 7..=9),
+        (2..=8, 3..=7),
+        (6..=6, 4..=6),
+        (2..=6, 4..=8),
+    ];
+
+    #[test]
+    fn example_counts_for_every_predicate() {
+        let counts = count_all_predicates(&EXAMPLE_PAIRS);
+
+        // contains, overlaps, disjoint, adjacent, equal
+        assert_eq!(counts, [2, 4, 2, 1, 0]);
+    }
+
+    #[test]
+    fn equal_ranges_contain_each_other() {
+        assert!(PairPredicate::Equal.holds(&(3..=5), &(3..=5)));
+        assert!(PairPredicate::Contains.holds(&(3..=5), &(3..=5)));
+        assert!(!PairPredicate::Adjacent.holds(&(3..=5), &(3..=5)));
+    }
+
+    #[test]
+    fn adjacency_at_the_end_of_u32() {
+        assert!(!PairPredicate::Adjacent.holds(&(5..=u32::MAX), &(0..=3)));
+        assert!(PairPredicate::Adjacent.holds(&(5..=u32::MAX), &(0..=4)));
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+use crate::parsing::LineAssignment;
+
+// Wide enough for the puzzle's sections 1 to 99, which take 296 characters.
+const MAX_ROW_WIDTH: usize = 320;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SectionViewError {
+    NoSections,
+    TooWide {
+        first: u32,
+        last: u32,
+        row_width: u64,
+    },
+}
+
+impl Display for SectionViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionViewError::NoSections => write!(f, "no sections are assigned"),
+            SectionViewError::TooWide {
+                first,
+                last,
+                row_width,
+            } => write!(
+                f,
+                "sections {} to {} need {} characters per row, at most {} can be shown",
+                first, last, row_width, MAX_ROW_WIDTH
+            ),
+        }
+    }
+}
+
+impl Error for SectionViewError {}
+
+// Sections shown on every row, so rows of different lines line up. Each
+// section takes as many characters as the widest section number, and wider
+// sections are separated by a space so neighbouring numbers stay apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionView {
+    first: u32,
+    last: u32,
+    width: usize,
+}
+
+impl SectionView {
+    // Like the puzzle, the view starts at section 1 unless some elf is
+    // assigned section 0.
+    pub fn for_assignments(
+        assignments: &[LineAssignment],
+    ) -> Result<SectionView, SectionViewError> {
+        let ranges = assignments.iter().flatten();
+        let (Some(first), Some(last)) = (
+            ranges.clone().map(|range| *range.start()).min(),
+            ranges.map(|range| *range.end()).max(),
+        ) else {
+            return Err(SectionViewError::NoSections);
+        };
+
+        let first = first.min(1);
+        let width = last.to_string().len();
+        let sections = u64::from(last - first) + 1;
+        let row_width = match width {
+            1 => sections,
+            _ => sections * (width as u64 + 1) - 1,
+        };
+        if row_width > MAX_ROW_WIDTH as u64 {
+            return Err(SectionViewError::TooWide {
+                first,
+                last,
+                row_width,
+            });
+        }
+
+        Ok(SectionView { first, last, width })
+    }
+
+    fn sections(&self) -> RangeInclusive<u32> {
+        self.first..=self.last
+    }
+
+    fn cell(&self, text: &str) -> String {
+        format!("{:>width$}", text, width = self.width)
+    }
+
+    fn empty_cell(&self) -> String {
+        ".".repeat(self.width)
+    }
+
+    fn row(&self, cells: impl Iterator<Item = String>) -> String {
+        let separator = if self.width > 1 { " " } else { "" };
+        cells.collect::<Vec<_>>().join(separator)
+    }
+}
+
+// One row per elf in the puzzle's dotted style, followed by a row marking
+// with `^` the sections more than one elf of the line is assigned.
+pub fn render_line_assignment(view: &SectionView, ranges: &[RangeInclusive<u32>]) -> String {
+    let mut rows: Vec<String> = ranges
+        .iter()
+        .map(|range| {
+            let cells = view.row(
+                view.sections()
+                    .map(|section| match range.contains(&section) {
+                        true => view.cell(&section.to_string()),
+                        false => view.empty_cell(),
+                    }),
+            );
+            format!("{}  {}-{}", cells, range.start(), range.end())
+        })
+        .collect();
+
+    let overlaps: Vec<bool> = view
+        .sections()
+        .map(|section| {
+            ranges
+                .iter()
+                .filter(|range| range.contains(&section))
+                .count()
+                > 1
+        })
+        .collect();
+    if overlaps.contains(&true) {
+        let markers = view.row(overlaps.into_iter().map(|overlap| match overlap {
+            true => "^".repeat(view.width),
+            false => " ".repeat(view.width),
+        }));
+        rows.push(markers.trim_end().to_string());
+    }
+
+    rows.join("\n")
+}
+
+// Number of elves of the whole input assigned each section, `.` for none and
+// `+` for more than fits in a cell.
+pub fn render_coverage_strip(view: &SectionView, assignments: &[LineAssignment]) -> String {
+    let length = (view.last - view.first) as usize + 1;
+    let mut changes = vec![0i64; length + 1];
+    for range in assignments
+        .iter()
+        .flatten()
+        .filter(|range| !range.is_empty())
+    {
+        changes[(range.start() - view.first) as usize] += 1;
+        changes[(range.end() - view.first) as usize + 1] -= 1;
+    }
+
+    let max_count = 10i64.pow(view.width as u32) - 1;
+    let mut count = 0;
+    view.row(changes[..length].iter().map(|change| {
+        count += change;
+        match count {
+            0 => view.empty_cell(),
+            _ if count > max_count => view.cell("+"),
+            _ => view.cell(&count.to_string()),
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_coverage_strip, render_line_assignment, SectionView, SectionViewError};
+    use crate::parsing::LineAssignment;
+
+    fn example() -> Vec<LineAssignment> {
+        vec![
+            vec![2..=4, 6..=8],
+            vec![2..=3, 4..=5],
+            vec![5..=7, 7..=9],
+            vec![2..=8, 3..=7],
+            vec![6..=6, 4..=6],
+            vec![2..=6, 4..=8],
+        ]
+    }
+
+    #[test]
+    fn pairs_are_drawn_like_in_the_puzzle() {
+        let assignments = example();
+        let view = SectionView::for_assignments(&assignments).unwrap();
+
+        assert_eq!(
+            render_line_assignment(&view, &assignments[0]),
+            ".234.....  2-4\n.....678.  6-8"
+        );
+        assert_eq!(
+            render_line_assignment(&view, &assignments[2]),
+            "....567..  5-7\n......789  7-9\n      ^"
+        );
+    }
+
+    #[test]
+    fn coverage_of_the_example() {
+        let assignments = example();
+        let view = SectionView::for_assignments(&assignments).unwrap();
+
+        assert_eq!(render_coverage_strip(&view, &assignments), ".45778641");
+    }
+
+    #[test]
+    fn wide_sections_take_several_characters() {
+        let assignments = vec![vec![9..=10, 10..=11]];
+        let view = SectionView::for_assignments(&assignments).unwrap();
+
+        assert_eq!(
+            render_line_assignment(&view, &assignments[0]),
+            ".. .. .. .. .. .. .. ..  9 10 ..  9-10\n.. .. .. .. .. .. .. .. .. 10 11  10-11\n                           ^^"
+        );
+        assert_eq!(
+            render_coverage_strip(&view, &assignments),
+            ".. .. .. .. .. .. .. ..  1  2  1"
+        );
+    }
+
+    #[test]
+    fn views_wider_than_a_row_are_rejected() {
+        let assignments = vec![
+            vec![1..=99, 50..=60],
+            vec![7..=7, 4_000_000_000..=4_000_000_000],
+        ];
+
+        assert!(SectionView::for_assignments(&assignments[..1]).is_ok());
+        assert_eq!(
+            SectionView::for_assignments(&assignments),
+            Err(SectionViewError::TooWide {
+                first: 1,
+                last: 4_000_000_000,
+                row_width: 43_999_999_999,
+            })
+        );
+        assert_eq!(
+            SectionView::for_assignments(&[]),
+            Err(SectionViewError::NoSections)
+        );
+    }
+}
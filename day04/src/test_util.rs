@@ -0,0 +1,11 @@
+// xorshift64, only used to make up assignments: each call returns a number
+// below `bound`.
+pub fn random_numbers(seed: u64) -> impl FnMut(u32) -> u32 {
+    let mut state = seed;
+    move |bound| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % bound as u64) as u32
+    }
+}